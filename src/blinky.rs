@@ -3,8 +3,10 @@
 //! This module separates the blinking logic from hardware dependencies,
 //! making it testable without actual hardware.
 
+use core::fmt::Write;
 use core::future::Future;
-use core::pin::Pin;
+
+use crate::BlinkyError;
 
 /// Trait for controlling an LED
 /// This abstraction allows us to test without real hardware
@@ -21,9 +23,31 @@ pub trait Led {
 
 /// Trait for async delays
 /// This abstraction allows us to test timing logic without real delays
+///
+/// Implementations can use `async fn delay_ms`. The returned future is not
+/// boxed and has no `Send` bound, so no allocator is needed.
 pub trait AsyncDelay {
     /// Delay for the specified number of milliseconds
-    fn delay_ms(&mut self, ms: u64) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+    fn delay_ms(&mut self, ms: u64) -> impl Future<Output = ()>;
+}
+
+/// `AsyncDelay` backed by the embassy time driver
+///
+/// ```ignore
+/// use active_note::blinky::{BlinkyConfig, BlinkyController, EmbassyDelay};
+///
+/// let mut controller = BlinkyController::new(BlinkyConfig::default()).unwrap();
+/// controller.run_async(&mut led, &mut EmbassyDelay).await;
+/// ```
+#[cfg(feature = "embassy")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmbassyDelay;
+
+#[cfg(feature = "embassy")]
+impl AsyncDelay for EmbassyDelay {
+    async fn delay_ms(&mut self, ms: u64) {
+        embassy_time::Timer::after_millis(ms).await;
+    }
 }
 
 /// Blinky pattern configuration
@@ -63,9 +87,66 @@ impl BlinkyConfig {
         self
     }
 
+    /// Convert to the top-level pattern config, dropping the blink count
+    ///
+    /// Durations beyond `u32::MAX` milliseconds saturate.
+    pub fn to_pattern_config(&self) -> crate::BlinkyConfig {
+        let narrow = |ms: u64| u32::try_from(ms).unwrap_or(u32::MAX);
+        crate::BlinkyConfig::new(narrow(self.on_duration_ms), narrow(self.off_duration_ms))
+    }
+
+    /// Compare against `other`, reporting how it differs from this config
+    pub fn diff(&self, other: &BlinkyConfig) -> ConfigDiff {
+        let delta = |from: u64, to: u64| {
+            (to as i128 - from as i128).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+        };
+
+        ConfigDiff {
+            on_delta_ms: delta(self.on_duration_ms, other.on_duration_ms),
+            off_delta_ms: delta(self.off_duration_ms, other.off_duration_ms),
+            count_changed: self.count != other.count,
+        }
+    }
+
+    /// Compare only the on and off durations, ignoring `count`
+    pub fn same_timing(&self, other: &BlinkyConfig) -> bool {
+        self.durations() == other.durations()
+    }
+
+    /// Get the `(on, off)` durations
+    pub fn durations(&self) -> (u64, u64) {
+        (self.on_duration_ms, self.off_duration_ms)
+    }
+
     /// Calculate the total period of one blink cycle
+    /// Saturates at `u64::MAX` rather than overflowing
     pub fn period_ms(&self) -> u64 {
-        self.on_duration_ms + self.off_duration_ms
+        self.on_duration_ms.saturating_add(self.off_duration_ms)
+    }
+
+    /// Calculate the total period of one blink cycle, or `None` on overflow
+    pub fn checked_period_ms(&self) -> Option<u64> {
+        self.on_duration_ms.checked_add(self.off_duration_ms)
+    }
+
+    /// Calculate the total period in microseconds, saturating at `u64::MAX`
+    pub fn period_us(&self) -> u64 {
+        self.period_ms().saturating_mul(1000)
+    }
+
+    /// Get the ON duration in microseconds, saturating at `u64::MAX`
+    pub fn on_duration_us(&self) -> u64 {
+        self.on_duration_ms.saturating_mul(1000)
+    }
+
+    /// Get the OFF duration in microseconds, saturating at `u64::MAX`
+    pub fn off_duration_us(&self) -> u64 {
+        self.off_duration_ms.saturating_mul(1000)
+    }
+
+    /// Calculate the period of one blink cycle in seconds
+    pub fn period_s(&self) -> f32 {
+        self.period_ms() as f32 / 1000.0
     }
 
     /// Calculate frequency in Hz (rounded)
@@ -73,6 +154,116 @@ impl BlinkyConfig {
         1000.0 / self.period_ms() as f32
     }
 
+    /// Calculate frequency in Hz, returning `None` for a zero period
+    ///
+    /// Safe to call on an unvalidated configuration.
+    pub fn checked_frequency_hz(&self) -> Option<f32> {
+        match self.period_ms() {
+            0 => None,
+            period => Some(1000.0 / period as f32),
+        }
+    }
+
+    /// Blink frequency above which the eye sees steady light
+    pub const MAX_VISIBLE_FREQUENCY_HZ: f32 = 50.0;
+
+    /// Check whether a viewer would actually see the LED blink
+    ///
+    /// False when the frequency is above `MAX_VISIBLE_FREQUENCY_HZ`, when
+    /// one state is so dominant the LED looks solid, or for a zero period.
+    pub fn is_visibly_blinking(&self) -> bool {
+        match self.checked_frequency_hz() {
+            Some(hz) if hz <= Self::MAX_VISIBLE_FREQUENCY_HZ => {
+                self.to_pattern_config().perceived_state().is_none()
+            }
+            _ => false,
+        }
+    }
+
+    /// MCU wakeups per second needed to service the pattern
+    ///
+    /// Two transitions per cycle; 0.0 for a zero period.
+    pub fn wakeups_per_second(&self) -> f32 {
+        self.checked_frequency_hz().map_or(0.0, |hz| 2.0 * hz)
+    }
+
+    /// Number of LED writes a finite run makes, one high and one low per blink
+    ///
+    /// Saturates at `u32::MAX`; `None` for an infinite pattern.
+    pub fn led_write_count(&self) -> Option<u32> {
+        self.count.map(|count| count.saturating_mul(2))
+    }
+
+    /// Number of whole cycles that fit in `target_ms`, or 0 for a zero period
+    pub fn count_for_runtime(&self, target_ms: u64) -> u32 {
+        match self.period_ms() {
+            0 => 0,
+            period => u32::try_from(target_ms / period).unwrap_or(u32::MAX),
+        }
+    }
+
+    /// Fraction of runtime the LED spends on, or 0.0 for a zero period
+    pub fn on_time_fraction(&self) -> f32 {
+        match self.period_ms() {
+            0 => 0.0,
+            period => self.on_duration_ms as f32 / period as f32,
+        }
+    }
+
+    /// Get the fundamental frequency and on-time fraction together
+    ///
+    /// Returns `(frequency_hz, duty_cycle)`, both 0.0 for a zero period.
+    pub fn spectrum_summary(&self) -> (f32, f32) {
+        (
+            self.checked_frequency_hz().unwrap_or(0.0),
+            self.on_time_fraction(),
+        )
+    }
+
+    /// Percentage of each period the LED is on, rounded to the nearest percent
+    ///
+    /// Returns 0 for a zero period.
+    pub fn duty_percent(&self) -> u8 {
+        match self.period_ms() {
+            0 => 0,
+            period => {
+                ((self.on_duration_ms as u128 * 100 + period as u128 / 2) / period as u128) as u8
+            }
+        }
+    }
+
+    /// Describe the pattern in human-readable form
+    ///
+    /// Produces text such as `"Blink 2.0Hz, 50% duty, 5 times"` or
+    /// `"Blink 1.0Hz, 25% duty, infinitely"`.
+    pub fn describe(&self) -> heapless::String<64> {
+        let mut text = heapless::String::new();
+        let frequency = self.checked_frequency_hz().unwrap_or(0.0);
+
+        // 64 bytes always fits the longest possible description
+        let _ = write!(
+            text,
+            "Blink {:.1}Hz, {}% duty, ",
+            frequency,
+            self.duty_percent()
+        );
+        let _ = match self.count {
+            Some(1) => write!(text, "once"),
+            Some(count) => write!(text, "{} times", count),
+            None => write!(text, "infinitely"),
+        };
+        text
+    }
+
+    /// Calculate the time-weighted average current over one cycle
+    ///
+    /// Includes the quiescent current drawn while the LED is off. A zero
+    /// period is treated as permanently off.
+    pub fn average_current_ma(&self, on_current_ma: f32, off_current_ma: f32) -> f32 {
+        let on_fraction = self.on_time_fraction();
+        on_current_ma * on_fraction + off_current_ma * (1.0 - on_fraction)
+    }
+
     /// Validate the configuration
     pub fn validate(&self) -> Result<(), &'static str> {
         if self.on_duration_ms == 0 && self.off_duration_ms == 0 {
@@ -87,18 +278,105 @@ impl BlinkyConfig {
     }
 }
 
+/// Validates configs while counting how many have been rejected
+///
+/// Lets firmware notice when it is repeatedly fed bad configs, for
+/// example to log a warning once the count passes a threshold.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConfigValidator {
+    failed_validations: u32,
+}
+
+impl ConfigValidator {
+    /// Create a validator with no recorded failures
+    pub const fn new() -> Self {
+        Self {
+            failed_validations: 0,
+        }
+    }
+
+    /// Validate `config`, counting the failure if it is rejected
+    pub fn validate(&mut self, config: &BlinkyConfig) -> Result<(), BlinkyError> {
+        config.validate().map_err(|_| {
+            self.failed_validations = self.failed_validations.saturating_add(1);
+            BlinkyError::InvalidConfig
+        })
+    }
+
+    /// Number of configs rejected so far, saturating at `u32::MAX`
+    pub fn failed_validations(&self) -> u32 {
+        self.failed_validations
+    }
+}
+
+/// Differences between two configurations, as produced by `BlinkyConfig::diff`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigDiff {
+    /// Change in on duration (milliseconds), other minus self
+    pub on_delta_ms: i64,
+    /// Change in off duration (milliseconds), other minus self
+    pub off_delta_ms: i64,
+    /// Whether the blink count differs
+    pub count_changed: bool,
+}
+
 /// State machine for the blinky pattern
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "embassy", derive(defmt::Format))]
 pub enum BlinkyState {
     On,
     Off,
 }
 
+impl From<crate::BlinkyState> for BlinkyState {
+    fn from(state: crate::BlinkyState) -> Self {
+        match state {
+            crate::BlinkyState::On => BlinkyState::On,
+            crate::BlinkyState::Off => BlinkyState::Off,
+        }
+    }
+}
+
+/// LED states driven by `BlinkyController::self_test`: on, off, then a
+/// triple blink
+pub const SELF_TEST_SEQUENCE: [BlinkyState; 8] = [
+    BlinkyState::On,
+    BlinkyState::Off,
+    BlinkyState::On,
+    BlinkyState::Off,
+    BlinkyState::On,
+    BlinkyState::Off,
+    BlinkyState::On,
+    BlinkyState::Off,
+];
+
+/// Snapshot of a controller's progress for structured logging
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "embassy", derive(defmt::Format))]
+pub struct ControllerStatus {
+    /// Current LED state
+    pub state: BlinkyState,
+    /// Completed blinks
+    pub blink_count: u32,
+    /// Whether the pattern will keep running
+    pub should_continue: bool,
+    /// Blinks left in a finite pattern (None = infinite)
+    pub remaining: Option<u32>,
+}
+
 /// Blinky controller that manages the blinking pattern
 pub struct BlinkyController {
     config: BlinkyConfig,
     state: BlinkyState,
     blink_count: u32,
+    speed_num: u32,
+    speed_den: u32,
+    min_toggle_interval_ms: u64,
+    last_toggle_ms: Option<u64>,
+    pending_phase_ms: u64,
+    used_fallback: bool,
+    max_runtime_ms: Option<u64>,
+    runtime_ms: u64,
 }
 
 impl BlinkyController {
@@ -109,9 +387,45 @@ impl BlinkyController {
             config,
             state: BlinkyState::Off,
             blink_count: 0,
+            speed_num: 1,
+            speed_den: 1,
+            min_toggle_interval_ms: 0,
+            last_toggle_ms: None,
+            pending_phase_ms: 0,
+            used_fallback: false,
+            max_runtime_ms: None,
+            runtime_ms: 0,
         })
     }
 
+    /// Create a controller, falling back to the default config if invalid
+    ///
+    /// Use `used_fallback` to find out which config was applied.
+    pub fn new_or_default(config: BlinkyConfig) -> Self {
+        match Self::new(config) {
+            Ok(controller) => controller,
+            Err(_) => {
+                let mut controller =
+                    Self::new(BlinkyConfig::default()).expect("default config is valid");
+                controller.used_fallback = true;
+                controller
+            }
+        }
+    }
+
+    /// Create a controller whose pattern is shifted in time by `phase_offset_ms`
+    ///
+    /// The first step holds the LED off for the offset before the normal
+    /// cycle begins, so several devices can be staggered.
+    pub fn new_with_phase(
+        config: BlinkyConfig,
+        phase_offset_ms: u64,
+    ) -> Result<Self, &'static str> {
+        let mut controller = Self::new(config)?;
+        controller.pending_phase_ms = phase_offset_ms;
+        Ok(controller)
+    }
+
     /// Get the current state
     pub fn state(&self) -> BlinkyState {
         self.state
@@ -127,8 +441,167 @@ impl BlinkyController {
         &self.config
     }
 
+    /// Check whether `new_or_default` had to fall back to the default config
+    pub fn used_fallback(&self) -> bool {
+        self.used_fallback
+    }
+
+    /// Reset the blink count and state so the pattern can run again
+    ///
+    /// Keeps the configuration and runtime settings, so a finished N-blink
+    /// acknowledgment can be retriggered without building a new controller.
+    pub fn rearm(&mut self) {
+        self.blink_count = 0;
+        self.state = BlinkyState::Off;
+        self.runtime_ms = 0;
+    }
+
+    /// Set an absolute runtime cap that applies even to infinite patterns
+    ///
+    /// The cap is enforced by `step_with_elapsed`. `None` removes it.
+    pub fn set_max_runtime_ms(&mut self, max_runtime_ms: Option<u64>) {
+        self.max_runtime_ms = max_runtime_ms;
+    }
+
+    /// Check whether the accumulated runtime has passed the cap
+    fn runtime_exceeded(&self) -> bool {
+        self.max_runtime_ms
+            .is_some_and(|max_ms| self.runtime_ms > max_ms)
+    }
+
+    /// Perform one step after `elapsed_ms` has passed, enforcing the runtime cap
+    ///
+    /// Elapsed time is accumulated across calls. Once it exceeds the cap
+    /// the LED is turned off and the pattern stops; `rearm` clears the
+    /// accumulated runtime.
+    pub fn step_with_elapsed<L: Led>(&mut self, led: &mut L, elapsed_ms: u64) -> Option<u64> {
+        self.runtime_ms = self.runtime_ms.saturating_add(elapsed_ms);
+        if self.runtime_exceeded() {
+            if self.state == BlinkyState::On {
+                led.set_low();
+                self.state = BlinkyState::Off;
+            }
+            return None;
+        }
+        self.step(led)
+    }
+
+    /// Turn the LED off and return the controller to the Off state
+    ///
+    /// `Drop` cannot reach the LED, so call this before discarding a
+    /// controller mid-pattern to avoid leaving the LED stuck on. The blink
+    /// count is kept; use `rearm` to start over.
+    pub fn finish<L: Led>(&mut self, led: &mut L) {
+        led.set_low();
+        self.state = BlinkyState::Off;
+    }
+
+    /// Drive the LED through the factory diagnostic sequence
+    ///
+    /// Writes each state in `SELF_TEST_SEQUENCE` back to back without any
+    /// delays and returns the states driven. The LED and controller are left
+    /// off; the blink count is not affected.
+    pub fn self_test<L: Led>(&mut self, led: &mut L) -> [BlinkyState; SELF_TEST_SEQUENCE.len()] {
+        for state in SELF_TEST_SEQUENCE {
+            Self::drive(led, state);
+        }
+        self.state = BlinkyState::Off;
+        SELF_TEST_SEQUENCE
+    }
+
+    /// Nudge the configured durations while keeping state and blink count
+    ///
+    /// Signed deltas are applied to each duration and the results are
+    /// clamped to at least 1ms. The adjusted config is validated before it
+    /// replaces the current one.
+    pub fn adjust_durations(
+        &mut self,
+        on_delta_ms: i64,
+        off_delta_ms: i64,
+    ) -> Result<(), BlinkyError> {
+        let adjust = |duration_ms: u64, delta_ms: i64| {
+            let adjusted = duration_ms as i128 + delta_ms as i128;
+            adjusted.clamp(1, u64::MAX as i128) as u64
+        };
+
+        let adjusted = BlinkyConfig {
+            on_duration_ms: adjust(self.config.on_duration_ms, on_delta_ms),
+            off_duration_ms: adjust(self.config.off_duration_ms, off_delta_ms),
+            ..self.config
+        };
+        adjusted
+            .validate()
+            .map_err(|_| BlinkyError::InvalidConfig)?;
+
+        self.config = adjusted;
+        Ok(())
+    }
+
+    /// Set a runtime speed multiplier of `num / den`
+    ///
+    /// Durations returned by `step` are scaled by `den / num`, so 2/1 blinks
+    /// twice as fast and 1/2 half as fast. The stored config is untouched;
+    /// set 1/1 to restore normal speed. A zero numerator or denominator is
+    /// ignored. Scaled durations never drop below 1ms.
+    pub fn set_speed_multiplier(&mut self, num: u32, den: u32) {
+        if num == 0 || den == 0 {
+            return;
+        }
+        self.speed_num = num;
+        self.speed_den = den;
+    }
+
+    /// Apply the speed multiplier to a configured duration
+    ///
+    /// Clamped to at least 1ms so the async runners never busy-spin on a
+    /// zero delay, whether from a zero configured duration or a large
+    /// multiplier.
+    fn scaled_duration(&self, duration_ms: u64) -> u64 {
+        (duration_ms.saturating_mul(self.speed_den as u64) / self.speed_num as u64).max(1)
+    }
+
+    /// Time left in a finite run once the current state's delay has elapsed
+    ///
+    /// Mid-cycle (LED on) this is the rest of the current cycle's off time
+    /// plus every remaining full cycle. Durations are scaled by the speed
+    /// multiplier, matching what `step` returns, and any phase offset not
+    /// yet consumed is included. Saturates at `u64::MAX`; `None` for an
+    /// infinite pattern.
+    pub fn remaining_runtime_ms(&self) -> Option<u64> {
+        let max = self.config.count?;
+        let remaining_blinks = max.saturating_sub(self.blink_count) as u64;
+        let period_ms = self
+            .scaled_duration(self.config.on_duration_ms)
+            .saturating_add(self.scaled_duration(self.config.off_duration_ms));
+
+        let runtime_ms = match self.state {
+            BlinkyState::On if remaining_blinks > 0 => self
+                .scaled_duration(self.config.off_duration_ms)
+                .saturating_add(period_ms.saturating_mul(remaining_blinks - 1)),
+            _ => period_ms.saturating_mul(remaining_blinks),
+        };
+        Some(runtime_ms.saturating_add(self.pending_phase_ms))
+    }
+
+    /// Capture the current progress in a single snapshot
+    pub fn status(&self) -> ControllerStatus {
+        ControllerStatus {
+            state: self.state,
+            blink_count: self.blink_count,
+            should_continue: self.should_continue(),
+            remaining: self
+                .config
+                .count
+                .map(|max| max.saturating_sub(self.blink_count)),
+        }
+    }
+
     /// Check if blinking should continue
     pub fn should_continue(&self) -> bool {
+        if self.runtime_exceeded() {
+            return false;
+        }
+
         match self.config.count {
             Some(max) => self.blink_count < max,
             None => true,
@@ -137,32 +610,104 @@ impl BlinkyController {
 
     /// Perform one blink cycle step
     /// Returns the duration to wait before the next step
+    ///
+    /// Returned durations are always at least 1ms, even for a zero duration
+    /// in the config or after speed scaling, so callers never wait zero.
     pub fn step<L: Led>(&mut self, led: &mut L) -> Option<u64> {
+        let (state, duration) = self.advance()?;
+        Self::drive(led, state);
+
+        if self.should_continue() {
+            Some(duration)
+        } else {
+            None
+        }
+    }
+
+    /// Perform exactly one step, for manual advancing such as a button press
+    ///
+    /// Returns the new state with its duration, which manual callers may
+    /// ignore. Unlike `step`, the final transition of a finite pattern is
+    /// still reported; `None` means nothing happened because the pattern
+    /// had already finished.
+    pub fn manual_step<L: Led>(&mut self, led: &mut L) -> Option<(BlinkyState, u64)> {
+        let (state, duration) = self.advance()?;
+        Self::drive(led, state);
+        Some((state, duration))
+    }
+
+    /// Advance the state machine without touching an LED
+    /// Returns the new state and how long to hold it
+    fn advance(&mut self) -> Option<(BlinkyState, u64)> {
         if !self.should_continue() {
             return None;
         }
 
-        match self.state {
+        Some(self.transition())
+    }
+
+    /// Advance the state machine regardless of the configured blink count
+    fn transition(&mut self) -> (BlinkyState, u64) {
+        if self.pending_phase_ms > 0 {
+            let phase_ms = core::mem::take(&mut self.pending_phase_ms);
+            return (self.state, phase_ms);
+        }
+
+        let duration_ms = match self.state {
             BlinkyState::Off => {
-                led.set_high();
                 self.state = BlinkyState::On;
-                Some(self.config.on_duration_ms)
+                self.config.on_duration_ms
             }
             BlinkyState::On => {
-                led.set_low();
                 self.state = BlinkyState::Off;
-                self.blink_count += 1;
+                self.blink_count = self.blink_count.saturating_add(1);
+                self.config.off_duration_ms
+            }
+        };
 
-                if self.should_continue() {
-                    Some(self.config.off_duration_ms)
-                } else {
-                    None
-                }
+        (self.state, self.scaled_duration(duration_ms))
+    }
+
+    /// Write a state to the LED
+    fn drive<L: Led>(led: &mut L, state: BlinkyState) {
+        match state {
+            BlinkyState::On => led.set_high(),
+            BlinkyState::Off => led.set_low(),
+        }
+    }
+
+    /// Set the minimum time that must pass between LED writes in `step_at`
+    pub fn set_min_toggle_interval_ms(&mut self, interval_ms: u64) {
+        self.min_toggle_interval_ms = interval_ms;
+    }
+
+    /// Perform one step at time `now_ms`, enforcing the minimum toggle interval
+    ///
+    /// If called again before the interval has elapsed since the last LED
+    /// toggle, the LED is left untouched and the remaining wait is returned.
+    /// Calls that do not change the state, such as the phase offset step,
+    /// do not restart the interval.
+    /// This protects the LED from misbehaving callers stepping too quickly.
+    pub fn step_at<L: Led>(&mut self, led: &mut L, now_ms: u64) -> Option<u64> {
+        if let Some(last_ms) = self.last_toggle_ms {
+            let elapsed_ms = now_ms.saturating_sub(last_ms);
+            if elapsed_ms < self.min_toggle_interval_ms && self.should_continue() {
+                return Some(self.min_toggle_interval_ms - elapsed_ms);
             }
         }
+
+        let previous = self.state;
+        let next = self.step(led);
+        if self.state != previous {
+            self.last_toggle_ms = Some(now_ms);
+        }
+        next
     }
 
     /// Run the complete blink pattern (async version)
+    ///
+    /// Every delay awaited is at least 1ms, as with `step`, so the loop
+    /// cannot busy-spin.
     pub async fn run_async<L, D>(&mut self, led: &mut L, delay: &mut D)
     where
         L: Led,
@@ -172,77 +717,1803 @@ impl BlinkyController {
             delay.delay_ms(duration).await;
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Flash an acknowledgment, then run the controller's own pattern
+    ///
+    /// Plays `ack_blinks` blinks with `ack_config`'s timing (its count is
+    /// ignored), including the final off gap, before handing over to
+    /// `run_async`. Ack durations are scaled by the speed multiplier and
+    /// clamped to at least 1ms, like those returned by `step`.
+    pub async fn run_with_ack_async<L, D>(
+        &mut self,
+        led: &mut L,
+        delay: &mut D,
+        ack_blinks: u32,
+        ack_config: BlinkyConfig,
+    ) where
+        L: Led,
+        D: AsyncDelay,
+    {
+        let ack = OneShotBurst::new(
+            ack_blinks,
+            ack_config.on_duration_ms,
+            ack_config.off_duration_ms,
+        );
+        for (state, duration) in ack {
+            Self::drive(led, state);
+            delay.delay_ms(self.scaled_duration(duration)).await;
+        }
 
-    #[test]
-    fn test_blinky_config_default() {
-        let config = BlinkyConfig::default();
-        assert_eq!(config.on_duration_ms, 500);
-        assert_eq!(config.off_duration_ms, 500);
-        assert_eq!(config.count, None);
+        self.run_async(led, delay).await;
     }
 
-    #[test]
-    fn test_blinky_config_period() {
-        let config = BlinkyConfig::new(300, 700);
-        assert_eq!(config.period_ms(), 1000);
-        assert_eq!(config.frequency_hz(), 1.0);
+    /// Step the pattern, awaiting delays, until the LED next turns on
+    ///
+    /// Returns as soon as the LED is switched on; the On duration itself is
+    /// not awaited. Fails with `BlinkyError::PatternFinished` if the pattern
+    /// ends first.
+    pub async fn wait_next_on_async<L, D>(
+        &mut self,
+        led: &mut L,
+        delay: &mut D,
+    ) -> Result<(), BlinkyError>
+    where
+        L: Led,
+        D: AsyncDelay,
+    {
+        loop {
+            let next = self.step(led);
+            if self.state == BlinkyState::On {
+                return Ok(());
+            }
+
+            match next {
+                Some(duration) => delay.delay_ms(duration).await,
+                None => return Err(BlinkyError::PatternFinished),
+            }
+        }
     }
 
-    #[test]
-    fn test_blinky_config_frequency() {
-        let config = BlinkyConfig::new(500, 500);
-        assert_eq!(config.frequency_hz(), 1.0);
+    /// Blink until `blink_count` reaches `target`, ignoring the configured count
+    ///
+    /// Useful when the stopping point is only known at runtime. Returns
+    /// immediately if the target has already been reached.
+    pub async fn run_until_count_async<L, D>(&mut self, led: &mut L, delay: &mut D, target: u32)
+    where
+        L: Led,
+        D: AsyncDelay,
+    {
+        while self.blink_count < target {
+            let (state, duration) = self.transition();
+            Self::drive(led, state);
 
-        let config = BlinkyConfig::new(250, 250);
-        assert_eq!(config.frequency_hz(), 2.0);
+            if self.blink_count >= target {
+                break;
+            }
+            delay.delay_ms(duration).await;
+        }
     }
 
-    #[test]
-    fn test_blinky_config_validation() {
-        let config = BlinkyConfig::new(100, 100);
-        assert!(config.validate().is_ok());
+    /// Run the complete blink pattern, reporting progress every `every` blinks
+    ///
+    /// `on_milestone` receives the blink count whenever a completed blink
+    /// brings it to a multiple of `every`. An `every` of zero never fires.
+    pub async fn run_async_with_milestone<L, D, F>(
+        &mut self,
+        led: &mut L,
+        delay: &mut D,
+        every: u32,
+        mut on_milestone: F,
+    ) where
+        L: Led,
+        D: AsyncDelay,
+        F: FnMut(u32),
+    {
+        loop {
+            let previous_count = self.blink_count;
+            let next = self.step(led);
 
-        let config = BlinkyConfig::new(0, 0);
-        assert!(config.validate().is_err());
+            if every != 0 && self.blink_count != previous_count && self.blink_count % every == 0 {
+                on_milestone(self.blink_count);
+            }
 
-        let config = BlinkyConfig::new(100, 100).with_count(0);
-        assert!(config.validate().is_err());
+            match next {
+                Some(duration) => delay.delay_ms(duration).await,
+                None => break,
+            }
+        }
     }
+}
 
-    #[test]
-    fn test_blinky_controller_creation() {
-        let config = BlinkyConfig::default();
-        let controller = BlinkyController::new(config);
-        assert!(controller.is_ok());
+/// Records every step a controller takes so the run can be replayed
+///
+/// Useful for regression tests against hardware captures: record a run,
+/// then replay it into a mock LED and compare.
+#[cfg(feature = "std")]
+pub struct PatternRecorder {
+    controller: BlinkyController,
+    steps: Vec<(BlinkyState, u64)>,
+}
 
-        let controller = controller.unwrap();
-        assert_eq!(controller.state(), BlinkyState::Off);
-        assert_eq!(controller.blink_count(), 0);
+#[cfg(feature = "std")]
+impl PatternRecorder {
+    /// Wrap a controller with an empty log
+    pub fn new(controller: BlinkyController) -> Self {
+        Self {
+            controller,
+            steps: Vec::new(),
+        }
     }
 
-    #[test]
-    fn test_blinky_controller_should_continue() {
-        let config = BlinkyConfig::default().with_count(3);
-        let mut controller = BlinkyController::new(config).unwrap();
+    /// Step the wrapped controller, logging the resulting state and duration
+    ///
+    /// Behaves like `BlinkyController::step`, but the final transition of a
+    /// finite run is logged too even though `None` is returned for it.
+    pub fn step<L: Led>(&mut self, led: &mut L) -> Option<u64> {
+        let (state, duration) = self.controller.advance()?;
+        BlinkyController::drive(led, state);
+        self.steps.push((state, duration));
 
-        assert!(controller.should_continue());
-        controller.blink_count = 2;
-        assert!(controller.should_continue());
-        controller.blink_count = 3;
-        assert!(!controller.should_continue());
+        if self.controller.should_continue() {
+            Some(duration)
+        } else {
+            None
+        }
     }
 
-    #[test]
-    fn test_blinky_controller_infinite() {
-        let config = BlinkyConfig::default();
-        let controller = BlinkyController::new(config).unwrap();
+    /// Get the recorded steps in order
+    pub fn steps(&self) -> &[(BlinkyState, u64)] {
+        &self.steps
+    }
 
-        // Should always continue with infinite count
-        assert!(controller.should_continue());
+    /// Get the wrapped controller
+    pub fn controller(&self) -> &BlinkyController {
+        &self.controller
+    }
+
+    /// Drive an LED through the recorded states without any delays
+    pub fn replay_into<L: Led>(&self, led: &mut L) {
+        for &(state, _) in &self.steps {
+            BlinkyController::drive(led, state);
+        }
+    }
+}
+
+/// A burst of quick flashes after which the LED stays off for good
+///
+/// Useful as a terminal indicator such as "boot complete": once the
+/// configured flashes are done, `next()` returns `None` and never resumes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OneShotBurst {
+    flashes: u32,
+    on_duration_ms: u64,
+    off_duration_ms: u64,
+    state: BlinkyState,
+    completed: u32,
+}
+
+impl OneShotBurst {
+    /// Create a burst of `flashes` blinks with the given timing
+    pub fn new(flashes: u32, on_duration_ms: u64, off_duration_ms: u64) -> Self {
+        Self {
+            flashes,
+            on_duration_ms,
+            off_duration_ms,
+            state: BlinkyState::Off,
+            completed: 0,
+        }
+    }
+
+    /// Check whether all flashes have been emitted
+    pub fn is_finished(&self) -> bool {
+        self.completed >= self.flashes
+    }
+}
+
+impl Iterator for OneShotBurst {
+    type Item = (BlinkyState, u64);
+
+    /// Advance the burst
+    /// Returns the new state and its duration, or `None` once finished
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_finished() {
+            return None;
+        }
+
+        match self.state {
+            BlinkyState::Off => {
+                self.state = BlinkyState::On;
+                Some((BlinkyState::On, self.on_duration_ms))
+            }
+            BlinkyState::On => {
+                self.state = BlinkyState::Off;
+                self.completed += 1;
+                Some((BlinkyState::Off, self.off_duration_ms))
+            }
+        }
+    }
+}
+
+/// Pattern that alternates between two configs on successive cycles
+///
+/// Odd cycles (the first, third, ...) use config A and even cycles use
+/// config B, giving rhythms like "dit-dah". Blink counts in either config
+/// are ignored; the pattern runs forever.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlternatingConfigPattern {
+    configs: [BlinkyConfig; 2],
+    active: usize,
+    state: BlinkyState,
+}
+
+impl AlternatingConfigPattern {
+    /// Create a pattern starting with config A
+    pub fn new(config_a: BlinkyConfig, config_b: BlinkyConfig) -> Self {
+        Self {
+            configs: [config_a, config_b],
+            active: 0,
+            state: BlinkyState::Off,
+        }
+    }
+
+    /// Get the config used for the current cycle
+    pub fn active_config(&self) -> &BlinkyConfig {
+        &self.configs[self.active]
+    }
+
+    /// Advance to the next state
+    /// Returns the new state and its duration from the active config
+    fn advance(&mut self) -> (BlinkyState, u64) {
+        match self.state {
+            BlinkyState::Off => {
+                self.state = BlinkyState::On;
+                (BlinkyState::On, self.active_config().on_duration_ms)
+            }
+            BlinkyState::On => {
+                self.state = BlinkyState::Off;
+                let duration = self.active_config().off_duration_ms;
+                self.active = 1 - self.active;
+                (BlinkyState::Off, duration)
+            }
+        }
+    }
+}
+
+impl Iterator for AlternatingConfigPattern {
+    type Item = (BlinkyState, u64);
+
+    /// Never returns `None`; the pattern runs forever
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.advance())
+    }
+}
+
+/// Pattern that rests for a few periods after every group of blinks
+///
+/// Without a rest configured it blinks like its config forever. With
+/// `with_rest`, the off state ending every `rest_after`-th blink is
+/// extended by `rest_cycles` full periods, giving rhythms like "blink 3,
+/// rest, blink 3". The config's blink count is ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestingPattern {
+    config: BlinkyConfig,
+    rest_after: u32,
+    rest_cycles: u32,
+    blinks_in_group: u32,
+    state: BlinkyState,
+}
+
+impl RestingPattern {
+    /// Create a pattern with no rests
+    pub fn new(config: BlinkyConfig) -> Self {
+        Self {
+            config,
+            rest_after: 0,
+            rest_cycles: 0,
+            blinks_in_group: 0,
+            state: BlinkyState::Off,
+        }
+    }
+
+    /// Create the conventional pattern for a device status
+    ///
+    /// Uses the timings from `crate::BlinkyConfig::for_status`, and rests
+    /// after every second blink while connecting to form a double blink.
+    pub fn for_status(status: crate::DeviceStatus) -> Self {
+        let timing = crate::BlinkyConfig::for_status(status);
+        let pattern = Self::new(BlinkyConfig::new(
+            timing.on_duration_ms as u64,
+            timing.off_duration_ms as u64,
+        ));
+        match status {
+            crate::DeviceStatus::Connecting => pattern.with_rest(2, 4),
+            _ => pattern,
+        }
+    }
+
+    /// Rest for `rest_cycles` periods after every `rest_after` blinks
+    ///
+    /// A `rest_after` of zero disables resting.
+    pub fn with_rest(mut self, rest_after: u32, rest_cycles: u32) -> Self {
+        self.rest_after = rest_after;
+        self.rest_cycles = rest_cycles;
+        self
+    }
+
+    /// Advance to the next state
+    /// Returns the new state and its duration, including any rest
+    fn advance(&mut self) -> (BlinkyState, u64) {
+        match self.state {
+            BlinkyState::Off => {
+                self.state = BlinkyState::On;
+                (BlinkyState::On, self.config.on_duration_ms)
+            }
+            BlinkyState::On => {
+                self.state = BlinkyState::Off;
+
+                let mut duration = self.config.off_duration_ms;
+                if self.rest_after == 0 {
+                    return (BlinkyState::Off, duration);
+                }
+
+                self.blinks_in_group += 1;
+                if self.blinks_in_group >= self.rest_after {
+                    self.blinks_in_group = 0;
+                    let rest_ms = self
+                        .config
+                        .period_ms()
+                        .saturating_mul(self.rest_cycles as u64);
+                    duration = duration.saturating_add(rest_ms);
+                }
+                (BlinkyState::Off, duration)
+            }
+        }
+    }
+}
+
+impl Iterator for RestingPattern {
+    type Item = (BlinkyState, u64);
+
+    /// Never returns `None`; the pattern runs forever
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.advance())
+    }
+}
+
+/// Pattern that blinks faster as a countdown approaches zero
+///
+/// The blink period shrinks linearly with the remaining time, from
+/// `start_period_ms` at the beginning to `end_period_ms` at expiry. Each
+/// period is split evenly between on and off, and the final state is cut
+/// short so the pattern ends exactly at `total_ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountdownPattern {
+    total_ms: u64,
+    start_period_ms: u64,
+    end_period_ms: u64,
+    elapsed_ms: u64,
+    period_ms: u64,
+    state: BlinkyState,
+}
+
+impl CountdownPattern {
+    /// Create a countdown lasting `total_ms`
+    ///
+    /// Periods are clamped to at least 2ms so both halves stay nonzero.
+    pub fn new(total_ms: u64, start_period_ms: u64, end_period_ms: u64) -> Self {
+        Self {
+            total_ms,
+            start_period_ms: start_period_ms.max(2),
+            end_period_ms: end_period_ms.max(2),
+            elapsed_ms: 0,
+            period_ms: 0,
+            state: BlinkyState::Off,
+        }
+    }
+
+    /// Time left before the countdown completes
+    pub fn remaining_ms(&self) -> u64 {
+        self.total_ms.saturating_sub(self.elapsed_ms)
+    }
+
+    /// Blink period for the cycle starting now
+    fn current_period_ms(&self) -> u64 {
+        if self.total_ms == 0 {
+            return self.end_period_ms;
+        }
+        let remaining = self.remaining_ms() as u128;
+        let start = self.start_period_ms as u128;
+        let end = self.end_period_ms as u128;
+        let total = self.total_ms as u128;
+
+        // Interpolate from end (nothing remaining) to start (everything remaining)
+        let period = if start >= end {
+            end + (start - end) * remaining / total
+        } else {
+            end - (end - start) * remaining / total
+        };
+        period as u64
+    }
+}
+
+impl Iterator for CountdownPattern {
+    type Item = (BlinkyState, u64);
+
+    /// Advance the countdown
+    /// Returns the new state and its duration, or `None` once time is up
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = self.remaining_ms();
+        if remaining == 0 {
+            return None;
+        }
+
+        let duration = match self.state {
+            BlinkyState::Off => {
+                self.period_ms = self.current_period_ms();
+                self.state = BlinkyState::On;
+                self.period_ms / 2
+            }
+            BlinkyState::On => {
+                self.state = BlinkyState::Off;
+                self.period_ms - self.period_ms / 2
+            }
+        }
+        .min(remaining);
+
+        self.elapsed_ms += duration;
+        Some((self.state, duration))
+    }
+}
+
+/// Acknowledgment pattern of `count` blinks that speed up as they go
+///
+/// The period is interpolated linearly from `start_period_ms` on the first
+/// blink to `end_period_ms` on the last, split evenly between on and off.
+/// Periods are clamped to at least 2ms so both halves stay nonzero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AcceleratingAckPattern {
+    count: u32,
+    start_period_ms: u64,
+    end_period_ms: u64,
+    state: BlinkyState,
+    completed: u32,
+}
+
+impl AcceleratingAckPattern {
+    /// Create an acknowledgment of `count` blinks
+    pub fn new(count: u32, start_period_ms: u64, end_period_ms: u64) -> Self {
+        Self {
+            count,
+            start_period_ms: start_period_ms.max(2),
+            end_period_ms: end_period_ms.max(2),
+            state: BlinkyState::Off,
+            completed: 0,
+        }
+    }
+
+    /// Check whether all blinks have been emitted
+    pub fn is_finished(&self) -> bool {
+        self.completed >= self.count
+    }
+
+    /// Blink period for the blink currently in progress
+    fn current_period_ms(&self) -> u64 {
+        if self.count <= 1 {
+            return self.start_period_ms;
+        }
+        let start = self.start_period_ms as u128;
+        let end = self.end_period_ms as u128;
+        let step = self.completed as u128;
+        let last = (self.count - 1) as u128;
+
+        let period = if start >= end {
+            start - (start - end) * step / last
+        } else {
+            start + (end - start) * step / last
+        };
+        period as u64
+    }
+}
+
+impl Iterator for AcceleratingAckPattern {
+    type Item = (BlinkyState, u64);
+
+    /// Advance the acknowledgment
+    /// Returns the new state and its duration, or `None` once finished
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_finished() {
+            return None;
+        }
+
+        let period = self.current_period_ms();
+        match self.state {
+            BlinkyState::Off => {
+                self.state = BlinkyState::On;
+                Some((BlinkyState::On, period / 2))
+            }
+            BlinkyState::On => {
+                self.state = BlinkyState::Off;
+                self.completed += 1;
+                Some((BlinkyState::Off, period - period / 2))
+            }
+        }
+    }
+}
+
+/// Charging indicator whose blink rate ramps up to a target and holds
+///
+/// The frequency is interpolated linearly from `start_freq` on the first
+/// cycle to `target_freq` after `ramp_cycles` cycles, then held forever.
+/// Each period is rounded to whole milliseconds, clamped to at least 2ms,
+/// and split evenly between on and off. Negative or NaN frequencies are
+/// treated as zero, which gives the longest possible period.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChargingPattern {
+    start_freq: f32,
+    target_freq: f32,
+    ramp_cycles: u32,
+    cycle: u32,
+    period_ms: u64,
+    state: BlinkyState,
+}
+
+impl ChargingPattern {
+    /// Create a pattern starting at `start_freq`
+    pub fn new(start_freq: f32, target_freq: f32, ramp_cycles: u32) -> Self {
+        Self {
+            start_freq: start_freq.max(0.0),
+            target_freq: target_freq.max(0.0),
+            ramp_cycles,
+            cycle: 0,
+            period_ms: 0,
+            state: BlinkyState::Off,
+        }
+    }
+
+    /// Blink frequency for the cycle starting now
+    pub fn current_freq(&self) -> f32 {
+        if self.cycle >= self.ramp_cycles {
+            return self.target_freq;
+        }
+        let t = self.cycle as f32 / self.ramp_cycles as f32;
+        self.start_freq + (self.target_freq - self.start_freq) * t
+    }
+
+    /// Advance to the next state
+    /// Returns the new state and its duration
+    fn advance(&mut self) -> (BlinkyState, u64) {
+        match self.state {
+            BlinkyState::Off => {
+                // Float-to-int casts saturate, so a zero frequency cannot wrap
+                self.period_ms = ((1000.0 / self.current_freq() + 0.5) as u64).max(2);
+                self.state = BlinkyState::On;
+                (BlinkyState::On, self.period_ms / 2)
+            }
+            BlinkyState::On => {
+                self.state = BlinkyState::Off;
+                self.cycle = self.cycle.saturating_add(1);
+                (BlinkyState::Off, self.period_ms - self.period_ms / 2)
+            }
+        }
+    }
+}
+
+impl Iterator for ChargingPattern {
+    type Item = (BlinkyState, u64);
+
+    /// Never returns `None`; the pattern runs forever
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.advance())
+    }
+}
+
+/// Greatest common divisor
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, or `None` on overflow
+fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Check whether sampling at `sample_hz` would misrepresent the pattern
+///
+/// True when the blink frequency is above the Nyquist limit of
+/// `sample_hz / 2`, including for a non-positive or NaN sample rate. A
+/// zero-period config never blinks and so cannot alias.
+pub fn will_alias(config: &BlinkyConfig, sample_hz: f32) -> bool {
+    match config.checked_frequency_hz() {
+        Some(hz) => sample_hz.is_nan() || hz > sample_hz / 2.0,
+        None => false,
+    }
+}
+
+/// Interval after which a whole ensemble of patterns repeats together
+///
+/// This is the least common multiple of every config's period. Returns
+/// `None` for an empty slice, if any period is zero, or on overflow.
+/// Blink counts are ignored.
+pub fn common_period_ms(configs: &[BlinkyConfig]) -> Option<u64> {
+    let (first, rest) = configs.split_first()?;
+    let mut common = first.period_ms();
+    for config in rest {
+        // A zero period makes the LCM zero, which is rejected below
+        common = checked_lcm(common, config.period_ms())?;
+    }
+    (common > 0).then_some(common)
+}
+
+/// Maximum number of `a`'s cycles `next_coincidence_ms` examines
+pub const COINCIDENCE_SEARCH_LIMIT: u64 = 100_000;
+
+/// Time until two patterns are next on simultaneously
+///
+/// Pattern `b` is shifted `phase_diff_ms` later than `a`; both are treated
+/// as already running and repeating forever. Returns `None` if their on
+/// windows never overlap or if either never turns on. Blink counts are
+/// ignored.
+///
+/// The search visits each of `a`'s on windows within one common period, so
+/// its cost grows with `b.period_ms() / gcd(a.period_ms(), b.period_ms())`.
+/// It gives up and returns `None` after `COINCIDENCE_SEARCH_LIMIT` of
+/// `a`'s cycles, or if the answer would not fit in a `u64`.
+pub fn next_coincidence_ms(a: &BlinkyConfig, b: &BlinkyConfig, phase_diff_ms: u64) -> Option<u64> {
+    let period_a = a.period_ms();
+    let period_b = b.period_ms();
+    if a.on_duration_ms == 0 || b.on_duration_ms == 0 {
+        return None;
+    }
+
+    // An overflowing common period is searched up to the largest time instead
+    let hyperperiod = checked_lcm(period_a, period_b).unwrap_or(u64::MAX);
+    let phase = phase_diff_ms % period_b;
+
+    let mut start_a: u64 = 0;
+    for _ in 0..COINCIDENCE_SEARCH_LIMIT {
+        if start_a >= hyperperiod {
+            break;
+        }
+
+        // Time since the most recent start of one of b's on windows
+        let offset = start_a % period_b;
+        let since_b = if offset >= phase {
+            offset - phase
+        } else {
+            offset + (period_b - phase)
+        };
+        if since_b < b.on_duration_ms {
+            return Some(start_a);
+        }
+
+        let next_b = start_a.checked_add(period_b - since_b)?;
+        if next_b < start_a.saturating_add(a.on_duration_ms) {
+            return Some(next_b);
+        }
+
+        start_a = start_a.checked_add(period_a)?;
+    }
+
+    None
+}
+
+/// Any built-in pattern, dispatched without trait objects or a heap
+pub enum PatternKind {
+    /// A finite or infinite controller-driven pattern
+    Controller(BlinkyController),
+    /// An endless pattern from the top-level pattern generator
+    Pattern(crate::BlinkyPattern),
+    /// A terminal burst of flashes
+    OneShotBurst(OneShotBurst),
+    /// Two configs alternating on successive cycles
+    Alternating(AlternatingConfigPattern),
+    /// A countdown that speeds up toward expiry
+    Countdown(CountdownPattern),
+    /// A finite burst of blinks that speeds up
+    AcceleratingAck(AcceleratingAckPattern),
+    /// Groups of blinks separated by rests
+    Resting(RestingPattern),
+    /// A blink rate that ramps up and holds
+    Charging(ChargingPattern),
+}
+
+impl PatternKind {}
+
+impl Iterator for PatternKind {
+    type Item = (BlinkyState, u64);
+
+    /// Advance the wrapped pattern
+    /// Returns the new state and its duration, or `None` once finished
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            PatternKind::Controller(controller) => controller.advance(),
+            PatternKind::Pattern(pattern) => {
+                let (state, duration_ms) = pattern.next();
+                Some((state.into(), duration_ms as u64))
+            }
+            PatternKind::OneShotBurst(burst) => burst.next(),
+            PatternKind::Alternating(pattern) => pattern.next(),
+            PatternKind::Countdown(pattern) => pattern.next(),
+            PatternKind::AcceleratingAck(pattern) => pattern.next(),
+            PatternKind::Resting(pattern) => pattern.next(),
+            PatternKind::Charging(pattern) => pattern.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{block_on, MockDelay};
+
+    /// LED that ignores all writes
+    struct NullLed;
+
+    /// LED that records every write as a bool (true = high)
+    struct RecordingLed {
+        writes: Vec<bool>,
+    }
+
+    impl RecordingLed {
+        fn new() -> Self {
+            Self { writes: Vec::new() }
+        }
+    }
+
+    impl Led for RecordingLed {
+        fn set_high(&mut self) {
+            self.writes.push(true);
+        }
+
+        fn set_low(&mut self) {
+            self.writes.push(false);
+        }
+
+        fn toggle(&mut self) {
+            let next = !self.writes.last().copied().unwrap_or(false);
+            self.writes.push(next);
+        }
+    }
+
+    impl Led for NullLed {
+        fn set_high(&mut self) {}
+        fn set_low(&mut self) {}
+        fn toggle(&mut self) {}
+    }
+
+    #[test]
+    fn test_blinky_config_default() {
+        let config = BlinkyConfig::default();
+        assert_eq!(config.on_duration_ms, 500);
+        assert_eq!(config.off_duration_ms, 500);
+        assert_eq!(config.count, None);
+    }
+
+    #[test]
+    fn test_blinky_config_period() {
+        let config = BlinkyConfig::new(300, 700);
+        assert_eq!(config.period_ms(), 1000);
+        assert_eq!(config.frequency_hz(), 1.0);
+    }
+
+    #[test]
+    fn test_blinky_config_frequency() {
+        let config = BlinkyConfig::new(500, 500);
+        assert_eq!(config.frequency_hz(), 1.0);
+
+        let config = BlinkyConfig::new(250, 250);
+        assert_eq!(config.frequency_hz(), 2.0);
+    }
+
+    #[test]
+    fn test_blinky_config_to_pattern_config() {
+        let config = BlinkyConfig::new(120, 340).with_count(4);
+        let pattern_config = config.to_pattern_config();
+        assert_eq!(pattern_config.on_duration_ms, 120);
+        assert_eq!(pattern_config.off_duration_ms, 340);
+
+        let round_trip = pattern_config.to_controller_config(config.count);
+        assert_eq!(round_trip, config);
+
+        let config = BlinkyConfig::new(u64::MAX, 10);
+        assert_eq!(config.to_pattern_config().on_duration_ms, u32::MAX);
+    }
+
+    #[test]
+    fn test_blinky_config_diff() {
+        let before = BlinkyConfig::new(200, 400);
+        let after = BlinkyConfig::new(300, 350).with_count(3);
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.on_delta_ms, 100);
+        assert_eq!(diff.off_delta_ms, -50);
+        assert!(diff.count_changed);
+
+        let diff = before.diff(&before);
+        assert_eq!(
+            diff,
+            ConfigDiff {
+                on_delta_ms: 0,
+                off_delta_ms: 0,
+                count_changed: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_blinky_config_durations() {
+        let (on, off) = BlinkyConfig::new(300, 700).with_count(2).durations();
+        assert_eq!(on, 300);
+        assert_eq!(off, 700);
+    }
+
+    #[test]
+    fn test_blinky_config_period_overflow() {
+        let config = BlinkyConfig::new(u64::MAX - 10, u64::MAX - 10);
+        assert_eq!(config.period_ms(), u64::MAX);
+        assert_eq!(config.checked_period_ms(), None);
+
+        let config = BlinkyConfig::new(300, 700);
+        assert_eq!(config.checked_period_ms(), Some(1000));
+    }
+
+    #[test]
+    fn test_blinky_config_period_s() {
+        assert_eq!(BlinkyConfig::new(300, 700).period_s(), 1.0);
+        assert_eq!(BlinkyConfig::new(125, 125).period_s(), 0.25);
+    }
+
+    #[test]
+    fn test_blinky_config_checked_frequency() {
+        let config = BlinkyConfig::new(0, 0);
+        assert_eq!(config.checked_frequency_hz(), None);
+
+        let config = BlinkyConfig::new(250, 250);
+        assert_eq!(config.checked_frequency_hz(), Some(2.0));
+    }
+
+    #[test]
+    fn test_blinky_config_wakeups_per_second() {
+        assert_eq!(BlinkyConfig::new(500, 500).wakeups_per_second(), 2.0);
+        assert_eq!(BlinkyConfig::new(250, 250).wakeups_per_second(), 4.0);
+        assert_eq!(BlinkyConfig::new(0, 0).wakeups_per_second(), 0.0);
+    }
+
+    #[test]
+    fn test_blinky_config_same_timing() {
+        let infinite = BlinkyConfig::new(100, 200);
+        let finite = BlinkyConfig::new(100, 200).with_count(3);
+
+        assert!(infinite.same_timing(&finite));
+        assert_ne!(infinite, finite);
+        assert!(!infinite.same_timing(&BlinkyConfig::new(200, 100)));
+    }
+
+    #[test]
+    fn test_blinky_config_led_write_count() {
+        assert_eq!(
+            BlinkyConfig::new(100, 100).with_count(5).led_write_count(),
+            Some(10)
+        );
+        assert_eq!(BlinkyConfig::new(100, 100).led_write_count(), None);
+        assert_eq!(
+            BlinkyConfig::new(100, 100)
+                .with_count(u32::MAX)
+                .led_write_count(),
+            Some(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn test_blinky_config_is_visibly_blinking() {
+        assert!(BlinkyConfig::new(500, 500).is_visibly_blinking());
+        assert!(BlinkyConfig::new(10, 10).is_visibly_blinking());
+
+        // 100Hz flicker fuses into steady light
+        assert!(!BlinkyConfig::new(5, 5).is_visibly_blinking());
+
+        // Slow enough, but effectively solid on
+        assert!(!BlinkyConfig::new(5000, 10).is_visibly_blinking());
+
+        assert!(!BlinkyConfig::new(0, 0).is_visibly_blinking());
+    }
+
+    #[test]
+    fn test_blinky_config_count_for_runtime() {
+        let config = BlinkyConfig::new(500, 500);
+        assert_eq!(config.count_for_runtime(10_000), 10);
+        assert_eq!(config.count_for_runtime(10_999), 10);
+        assert_eq!(config.count_for_runtime(999), 0);
+
+        assert_eq!(BlinkyConfig::new(0, 0).count_for_runtime(10_000), 0);
+    }
+
+    #[test]
+    fn test_blinky_config_on_time_fraction() {
+        assert_eq!(BlinkyConfig::new(500, 500).on_time_fraction(), 0.5);
+        assert_eq!(BlinkyConfig::new(750, 250).on_time_fraction(), 0.75);
+
+        // Asymmetric heartbeat-style pulse
+        let heartbeat = BlinkyConfig::new(100, 900).with_count(5);
+        assert_eq!(heartbeat.on_time_fraction(), 0.1);
+
+        assert_eq!(BlinkyConfig::new(0, 0).on_time_fraction(), 0.0);
+    }
+
+    #[test]
+    fn test_blinky_config_microseconds() {
+        let config = BlinkyConfig::new(250, 750);
+        assert_eq!(config.on_duration_us(), 250_000);
+        assert_eq!(config.off_duration_us(), 750_000);
+        assert_eq!(config.period_us(), 1_000_000);
+
+        let huge = BlinkyConfig::new(u64::MAX / 1000 + 1, 1);
+        assert_eq!(huge.on_duration_us(), u64::MAX);
+        assert_eq!(huge.off_duration_us(), 1000);
+        assert_eq!(huge.period_us(), u64::MAX);
+    }
+
+    #[test]
+    fn test_blinky_config_spectrum_summary() {
+        assert_eq!(BlinkyConfig::new(250, 250).spectrum_summary(), (2.0, 0.5));
+        assert_eq!(BlinkyConfig::new(100, 900).spectrum_summary(), (1.0, 0.1));
+        assert_eq!(BlinkyConfig::new(0, 0).spectrum_summary(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_blinky_config_duty_percent() {
+        assert_eq!(BlinkyConfig::new(500, 500).duty_percent(), 50);
+        assert_eq!(BlinkyConfig::new(750, 250).duty_percent(), 75);
+        assert_eq!(BlinkyConfig::new(100, 900).duty_percent(), 10);
+        assert_eq!(BlinkyConfig::new(1, 2).duty_percent(), 33);
+        assert_eq!(BlinkyConfig::new(0, 0).duty_percent(), 0);
+    }
+
+    #[test]
+    fn test_blinky_config_describe() {
+        let config = BlinkyConfig::new(250, 250).with_count(5);
+        assert_eq!(config.describe().as_str(), "Blink 2.0Hz, 50% duty, 5 times");
+
+        let config = BlinkyConfig::new(250, 750);
+        assert_eq!(
+            config.describe().as_str(),
+            "Blink 1.0Hz, 25% duty, infinitely"
+        );
+
+        let config = BlinkyConfig::new(500, 500).with_count(1);
+        assert_eq!(config.describe().as_str(), "Blink 1.0Hz, 50% duty, once");
+    }
+
+    #[test]
+    fn test_blinky_config_average_current() {
+        let config = BlinkyConfig::new(500, 500);
+        assert_eq!(config.average_current_ma(10.0, 0.0), 5.0);
+        assert_eq!(config.average_current_ma(10.0, 2.0), 6.0);
+
+        let config = BlinkyConfig::new(250, 750);
+        assert_eq!(config.average_current_ma(20.0, 4.0), 8.0);
+
+        let config = BlinkyConfig::new(0, 0);
+        assert_eq!(config.average_current_ma(20.0, 4.0), 4.0);
+    }
+
+    #[test]
+    fn test_blinky_config_validation() {
+        let config = BlinkyConfig::new(100, 100);
+        assert!(config.validate().is_ok());
+
+        let config = BlinkyConfig::new(0, 0);
+        assert!(config.validate().is_err());
+
+        let config = BlinkyConfig::new(100, 100).with_count(0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_blinky_controller_creation() {
+        let config = BlinkyConfig::default();
+        let controller = BlinkyController::new(config);
+        assert!(controller.is_ok());
+
+        let controller = controller.unwrap();
+        assert_eq!(controller.state(), BlinkyState::Off);
+        assert_eq!(controller.blink_count(), 0);
+    }
+
+    #[test]
+    fn test_blinky_controller_should_continue() {
+        let config = BlinkyConfig::default().with_count(3);
+        let mut controller = BlinkyController::new(config).unwrap();
+
+        assert!(controller.should_continue());
+        controller.blink_count = 2;
+        assert!(controller.should_continue());
+        controller.blink_count = 3;
+        assert!(!controller.should_continue());
+    }
+
+    #[test]
+    fn test_blinky_controller_infinite() {
+        let config = BlinkyConfig::default();
+        let controller = BlinkyController::new(config).unwrap();
+
+        // Should always continue with infinite count
+        assert!(controller.should_continue());
+    }
+
+    #[test]
+    fn test_blinky_controller_speed_multiplier() {
+        let config = BlinkyConfig::new(200, 400);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = NullLed;
+
+        controller.set_speed_multiplier(2, 1);
+        assert_eq!(controller.step(&mut led), Some(100));
+        assert_eq!(controller.step(&mut led), Some(200));
+
+        controller.set_speed_multiplier(1, 2);
+        assert_eq!(controller.step(&mut led), Some(400));
+        assert_eq!(controller.step(&mut led), Some(800));
+
+        controller.set_speed_multiplier(1, 1);
+        assert_eq!(controller.step(&mut led), Some(200));
+        assert_eq!(controller.config().on_duration_ms, 200);
+    }
+
+    #[test]
+    fn test_blinky_controller_speed_multiplier_ignores_zero() {
+        let config = BlinkyConfig::new(200, 400);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = NullLed;
+
+        controller.set_speed_multiplier(2, 1);
+        controller.set_speed_multiplier(1, 0);
+        controller.set_speed_multiplier(0, 1);
+        assert_eq!(controller.step(&mut led), Some(100));
+    }
+
+    #[test]
+    fn test_blinky_controller_zero_duration_clamped() {
+        let config = BlinkyConfig::new(0, 500).with_count(2);
+        assert!(config.validate().is_ok());
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = NullLed;
+        let mut delay = MockDelay::new();
+
+        assert_eq!(controller.step(&mut led), Some(1));
+        assert_eq!(controller.step(&mut led), Some(500));
+
+        controller.rearm();
+        block_on(controller.run_async(&mut led, &mut delay));
+        assert_eq!(delay.delays, vec![1, 500, 1]);
+    }
+
+    #[test]
+    fn test_blinky_controller_speed_multiplier_never_zero() {
+        let config = BlinkyConfig::new(1, 3).with_count(2);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = NullLed;
+        let mut delay = MockDelay::new();
+
+        controller.set_speed_multiplier(u32::MAX, 1);
+        assert_eq!(controller.step(&mut led), Some(1));
+        assert_eq!(controller.step(&mut led), Some(1));
+
+        controller.rearm();
+        block_on(controller.run_async(&mut led, &mut delay));
+        assert!(!delay.delays.is_empty());
+        assert!(delay.delays.iter().all(|&ms| ms == 1));
+    }
+
+    #[test]
+    fn test_blinky_controller_run_with_ack_async() {
+        let config = BlinkyConfig::new(100, 200).with_count(2);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = RecordingLed::new();
+        let mut delay = MockDelay::new();
+
+        block_on(controller.run_with_ack_async(&mut led, &mut delay, 2, BlinkyConfig::new(50, 50)));
+
+        assert_eq!(delay.delays, vec![50, 50, 50, 50, 100, 200, 100]);
+        assert_eq!(
+            led.writes,
+            vec![true, false, true, false, true, false, true, false]
+        );
+        assert_eq!(controller.blink_count(), 2);
+    }
+
+    #[test]
+    fn test_blinky_controller_run_with_ack_async_scales_and_clamps() {
+        let config = BlinkyConfig::new(100, 200).with_count(1);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = NullLed;
+        let mut delay = MockDelay::new();
+
+        block_on(controller.run_with_ack_async(&mut led, &mut delay, 2, BlinkyConfig::new(0, 0)));
+        assert_eq!(delay.delays, vec![1, 1, 1, 1, 100]);
+        assert!(delay.delays.iter().all(|&ms| ms >= 1));
+
+        controller.rearm();
+        controller.set_speed_multiplier(2, 1);
+        let mut delay = MockDelay::new();
+        block_on(controller.run_with_ack_async(&mut led, &mut delay, 1, BlinkyConfig::new(50, 80)));
+        assert_eq!(delay.delays, vec![25, 40, 50]);
+    }
+
+    #[test]
+    fn test_blinky_controller_run_async_with_milestone() {
+        let config = BlinkyConfig::new(100, 200).with_count(7);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = NullLed;
+        let mut delay = MockDelay::new();
+        let mut milestones = Vec::new();
+
+        block_on(
+            controller
+                .run_async_with_milestone(&mut led, &mut delay, 3, |count| milestones.push(count)),
+        );
+
+        assert_eq!(milestones, vec![3, 6]);
+        assert_eq!(controller.blink_count(), 7);
+        assert_eq!(delay.delays.len(), 13);
+    }
+
+    #[test]
+    fn test_blinky_controller_run_async_with_milestone_zero_every() {
+        let config = BlinkyConfig::new(100, 200).with_count(3);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = NullLed;
+        let mut delay = MockDelay::new();
+        let mut fired = false;
+
+        block_on(controller.run_async_with_milestone(&mut led, &mut delay, 0, |_| fired = true));
+
+        assert!(!fired);
+        assert_eq!(controller.blink_count(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_pattern_recorder_replay() {
+        let config = BlinkyConfig::new(100, 200).with_count(3);
+        let mut recorder = PatternRecorder::new(BlinkyController::new(config).unwrap());
+        let mut hardware = RecordingLed::new();
+
+        while recorder.step(&mut hardware).is_some() {}
+
+        assert_eq!(recorder.steps().len(), 6);
+        assert_eq!(recorder.steps()[0], (BlinkyState::On, 100));
+        assert_eq!(recorder.steps()[1], (BlinkyState::Off, 200));
+        assert_eq!(recorder.controller().blink_count(), 3);
+
+        let mut mock = RecordingLed::new();
+        recorder.replay_into(&mut mock);
+        assert_eq!(mock.writes, hardware.writes);
+        assert_eq!(mock.writes, vec![true, false, true, false, true, false]);
+    }
+
+    #[test]
+    fn test_blinky_controller_remaining_runtime() {
+        let config = BlinkyConfig::new(100, 200).with_count(3);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = NullLed;
+
+        assert_eq!(controller.remaining_runtime_ms(), Some(900));
+
+        controller.step(&mut led);
+        assert_eq!(controller.remaining_runtime_ms(), Some(800));
+        controller.step(&mut led);
+        assert_eq!(controller.remaining_runtime_ms(), Some(600));
+
+        controller.step(&mut led);
+        controller.step(&mut led);
+        controller.step(&mut led);
+        assert_eq!(controller.remaining_runtime_ms(), Some(200));
+        controller.step(&mut led);
+        assert_eq!(controller.remaining_runtime_ms(), Some(0));
+
+        let infinite = BlinkyController::new(BlinkyConfig::new(100, 200)).unwrap();
+        assert_eq!(infinite.remaining_runtime_ms(), None);
+    }
+
+    #[test]
+    fn test_pattern_kind_dispatch() {
+        let controller = BlinkyController::new(BlinkyConfig::new(100, 200).with_count(1)).unwrap();
+        let mut pattern = PatternKind::Controller(controller);
+
+        assert_eq!(pattern.next(), Some((BlinkyState::On, 100)));
+        assert_eq!(pattern.next(), Some((BlinkyState::Off, 200)));
+        assert_eq!(pattern.next(), None);
+
+        pattern = PatternKind::Pattern(crate::BlinkyPattern::new(crate::BlinkyConfig::new(
+            300, 400,
+        )));
+
+        assert_eq!(pattern.next(), Some((BlinkyState::On, 300)));
+        assert_eq!(pattern.next(), Some((BlinkyState::Off, 400)));
+        assert_eq!(pattern.next(), Some((BlinkyState::On, 300)));
+    }
+
+    #[test]
+    fn test_one_shot_burst_terminates() {
+        let mut burst = OneShotBurst::new(3, 50, 50);
+
+        for _ in 0..3 {
+            assert_eq!(burst.next(), Some((BlinkyState::On, 50)));
+            assert_eq!(burst.next(), Some((BlinkyState::Off, 50)));
+        }
+
+        assert!(burst.is_finished());
+        for _ in 0..5 {
+            assert_eq!(burst.next(), None);
+        }
+    }
+
+    #[test]
+    fn test_blinky_controller_min_toggle_interval() {
+        let config = BlinkyConfig::new(100, 100);
+        let mut controller = BlinkyController::new(config).unwrap();
+        controller.set_min_toggle_interval_ms(50);
+        let mut led = RecordingLed::new();
+
+        assert_eq!(controller.step_at(&mut led, 0), Some(100));
+        assert_eq!(led.writes, vec![true]);
+
+        // Too soon: suppressed, reports remaining wait
+        assert_eq!(controller.step_at(&mut led, 20), Some(30));
+        assert_eq!(controller.step_at(&mut led, 49), Some(1));
+        assert_eq!(led.writes, vec![true]);
+        assert_eq!(controller.state(), BlinkyState::On);
+
+        // Interval elapsed: toggles again
+        assert_eq!(controller.step_at(&mut led, 50), Some(100));
+        assert_eq!(led.writes, vec![true, false]);
+        assert_eq!(controller.state(), BlinkyState::Off);
+    }
+
+    #[test]
+    fn test_blinky_controller_min_toggle_interval_polling() {
+        let config = BlinkyConfig::new(100, 100);
+        let mut controller = BlinkyController::new_with_phase(config, 30).unwrap();
+        controller.set_min_toggle_interval_ms(50);
+        let mut led = RecordingLed::new();
+
+        // The phase step holds the LED off without toggling it
+        assert_eq!(controller.step_at(&mut led, 0), Some(30));
+        assert_eq!(controller.step_at(&mut led, 30), Some(100));
+        assert_eq!(led.writes, vec![false, true]);
+
+        // Polling between toggles does not push the next one back
+        assert_eq!(controller.step_at(&mut led, 40), Some(40));
+        assert_eq!(controller.step_at(&mut led, 60), Some(20));
+        assert_eq!(controller.step_at(&mut led, 79), Some(1));
+        assert_eq!(led.writes, vec![false, true]);
+
+        assert_eq!(controller.step_at(&mut led, 80), Some(100));
+        assert_eq!(led.writes, vec![false, true, false]);
+    }
+
+    #[test]
+    fn test_blinky_controller_rearm() {
+        let config = BlinkyConfig::new(100, 100).with_count(2);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = RecordingLed::new();
+
+        while controller.step(&mut led).is_some() {}
+        assert!(!controller.should_continue());
+        assert_eq!(controller.step(&mut led), None);
+
+        controller.rearm();
+        assert!(controller.should_continue());
+        assert_eq!(controller.blink_count(), 0);
+        assert_eq!(controller.state(), BlinkyState::Off);
+
+        assert_eq!(controller.step(&mut led), Some(100));
+        assert_eq!(led.writes.last(), Some(&true));
+        assert_eq!(controller.config(), &config);
+    }
+
+    #[test]
+    fn test_alternating_config_pattern() {
+        let dit = BlinkyConfig::new(100, 100);
+        let dah = BlinkyConfig::new(300, 100);
+        let mut pattern = AlternatingConfigPattern::new(dit, dah);
+
+        // Cycle 1 uses config A
+        assert_eq!(pattern.next(), Some((BlinkyState::On, 100)));
+        assert_eq!(pattern.next(), Some((BlinkyState::Off, 100)));
+
+        // Cycle 2 uses config B
+        assert_eq!(pattern.next(), Some((BlinkyState::On, 300)));
+        assert_eq!(pattern.next(), Some((BlinkyState::Off, 100)));
+
+        // Cycle 3 is back to config A
+        assert_eq!(pattern.active_config(), &dit);
+        assert_eq!(pattern.next(), Some((BlinkyState::On, 100)));
+    }
+
+    #[test]
+    fn test_blinky_controller_phase_offset() {
+        let config = BlinkyConfig::new(200, 200);
+        let mut controller = BlinkyController::new_with_phase(config, 200).unwrap();
+        let mut led = RecordingLed::new();
+
+        // Initial partial delay keeps the LED off
+        assert_eq!(controller.step(&mut led), Some(200));
+        assert_eq!(controller.state(), BlinkyState::Off);
+        assert_eq!(led.writes, vec![false]);
+
+        // Normal cycle follows
+        assert_eq!(controller.step(&mut led), Some(200));
+        assert_eq!(controller.state(), BlinkyState::On);
+        assert_eq!(controller.step(&mut led), Some(200));
+        assert_eq!(controller.blink_count(), 1);
+    }
+
+    #[test]
+    fn test_blinky_controller_zero_phase_offset() {
+        let config = BlinkyConfig::new(200, 300);
+        let mut controller = BlinkyController::new_with_phase(config, 0).unwrap();
+        let mut led = NullLed;
+
+        assert_eq!(controller.step(&mut led), Some(200));
+        assert_eq!(controller.state(), BlinkyState::On);
+    }
+
+    #[test]
+    fn test_blinky_controller_self_test() {
+        let config = BlinkyConfig::new(100, 100).with_count(3);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = RecordingLed::new();
+
+        let states = controller.self_test(&mut led);
+
+        use BlinkyState::{Off, On};
+        assert_eq!(states, [On, Off, On, Off, On, Off, On, Off]);
+        assert_eq!(
+            led.writes,
+            vec![true, false, true, false, true, false, true, false]
+        );
+        assert_eq!(controller.state(), Off);
+        assert_eq!(controller.blink_count(), 0);
+    }
+
+    #[test]
+    fn test_blinky_controller_adjust_durations() {
+        let config = BlinkyConfig::new(200, 400).with_count(5);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = NullLed;
+
+        controller.step(&mut led);
+        controller.step(&mut led);
+
+        controller.adjust_durations(50, -100).unwrap();
+        assert_eq!(controller.config().on_duration_ms, 250);
+        assert_eq!(controller.config().off_duration_ms, 300);
+        assert_eq!(controller.config().count, Some(5));
+
+        // State and count are preserved
+        assert_eq!(controller.blink_count(), 1);
+        assert_eq!(controller.state(), BlinkyState::Off);
+        assert_eq!(controller.step(&mut led), Some(250));
+    }
+
+    #[test]
+    fn test_blinky_controller_adjust_durations_clamps() {
+        let config = BlinkyConfig::new(200, 400);
+        let mut controller = BlinkyController::new(config).unwrap();
+
+        controller.adjust_durations(-1000, i64::MIN).unwrap();
+        assert_eq!(controller.config().on_duration_ms, 1);
+        assert_eq!(controller.config().off_duration_ms, 1);
+
+        controller.adjust_durations(i64::MAX, 0).unwrap();
+        assert_eq!(controller.config().on_duration_ms, i64::MAX as u64 + 1);
+    }
+
+    #[test]
+    fn test_blinky_controller_new_or_default() {
+        let config = BlinkyConfig::new(100, 200).with_count(2);
+        let controller = BlinkyController::new_or_default(config);
+        assert!(!controller.used_fallback());
+        assert_eq!(controller.config(), &config);
+
+        let controller = BlinkyController::new_or_default(BlinkyConfig::new(0, 0));
+        assert!(controller.used_fallback());
+        assert_eq!(controller.config(), &BlinkyConfig::default());
+    }
+
+    #[test]
+    fn test_resting_pattern_rests_between_groups() {
+        let mut pattern = RestingPattern::new(BlinkyConfig::new(100, 100)).with_rest(3, 2);
+
+        for _ in 0..2 {
+            for _ in 0..2 {
+                assert_eq!(pattern.next(), Some((BlinkyState::On, 100)));
+                assert_eq!(pattern.next(), Some((BlinkyState::Off, 100)));
+            }
+            assert_eq!(pattern.next(), Some((BlinkyState::On, 100)));
+            assert_eq!(pattern.next(), Some((BlinkyState::Off, 500)));
+        }
+    }
+
+    #[test]
+    fn test_resting_pattern_for_status_double_blink() {
+        let mut connecting = RestingPattern::for_status(crate::DeviceStatus::Connecting);
+        for _ in 0..3 {
+            assert_eq!(connecting.next(), Some((BlinkyState::On, 60)));
+            assert_eq!(connecting.next(), Some((BlinkyState::Off, 140)));
+            assert_eq!(connecting.next(), Some((BlinkyState::On, 60)));
+            assert_eq!(connecting.next(), Some((BlinkyState::Off, 940)));
+        }
+
+        // Other statuses blink steadily with their preset timings
+        let mut error = RestingPattern::for_status(crate::DeviceStatus::Error);
+        for _ in 0..3 {
+            assert_eq!(error.next(), Some((BlinkyState::On, 100)));
+            assert_eq!(error.next(), Some((BlinkyState::Off, 100)));
+        }
+    }
+
+    #[test]
+    fn test_resting_pattern_without_rest() {
+        let mut pattern = RestingPattern::new(BlinkyConfig::new(100, 200)).with_rest(0, 5);
+
+        // No rest step ever appears, however many blinks go by
+        for _ in 0..10_000 {
+            assert_eq!(pattern.next(), Some((BlinkyState::On, 100)));
+            assert_eq!(pattern.next(), Some((BlinkyState::Off, 200)));
+        }
+    }
+
+    #[test]
+    fn test_countdown_pattern_speeds_up() {
+        let countdown = CountdownPattern::new(10_000, 1000, 100);
+        let mut on_durations = Vec::new();
+        let mut total = 0;
+
+        for (state, duration) in countdown {
+            assert!(duration > 0);
+            if state == BlinkyState::On {
+                on_durations.push(duration);
+            }
+            total += duration;
+        }
+
+        assert_eq!(total, 10_000);
+        assert_eq!(on_durations[0], 500);
+        assert!(on_durations.windows(2).all(|w| w[0] >= w[1]));
+        assert!(on_durations.last().unwrap() < &on_durations[0]);
+    }
+
+    #[test]
+    fn test_countdown_pattern_terminates() {
+        let mut countdown = CountdownPattern::new(1000, 200, 20);
+        let steps = core::iter::from_fn(|| countdown.next())
+            .take(10_000)
+            .count();
+
+        assert!(steps < 10_000);
+        assert_eq!(countdown.remaining_ms(), 0);
+        assert_eq!(countdown.next(), None);
+    }
+
+    #[test]
+    fn test_accelerating_ack_pattern() {
+        let mut ack = AcceleratingAckPattern::new(4, 400, 100);
+        let mut periods = Vec::new();
+
+        while let Some((state, on_ms)) = ack.next() {
+            assert_eq!(state, BlinkyState::On);
+            let (state, off_ms) = ack.next().unwrap();
+            assert_eq!(state, BlinkyState::Off);
+            periods.push(on_ms + off_ms);
+        }
+
+        assert_eq!(periods, vec![400, 300, 200, 100]);
+        assert!(ack.is_finished());
+        assert_eq!(ack.next(), None);
+    }
+
+    #[test]
+    fn test_accelerating_ack_pattern_single_blink() {
+        let mut ack = AcceleratingAckPattern::new(1, 300, 100);
+
+        assert_eq!(ack.next(), Some((BlinkyState::On, 150)));
+        assert_eq!(ack.next(), Some((BlinkyState::Off, 150)));
+        assert_eq!(ack.next(), None);
+    }
+
+    #[test]
+    fn test_charging_pattern_ramps_then_holds() {
+        let mut pattern = ChargingPattern::new(1.0, 5.0, 4);
+        let mut periods = Vec::new();
+
+        for _ in 0..8 {
+            let (_, on_ms) = pattern.next().unwrap();
+            let (_, off_ms) = pattern.next().unwrap();
+            periods.push(on_ms + off_ms);
+        }
+
+        assert_eq!(&periods[..5], &[1000, 500, 333, 250, 200]);
+        assert!(periods[..5].windows(2).all(|w| w[0] > w[1]));
+        assert!(periods[4..].iter().all(|&period| period == 200));
+        assert_eq!(pattern.current_freq(), 5.0);
+    }
+
+    #[test]
+    fn test_charging_pattern_without_ramp() {
+        let mut pattern = ChargingPattern::new(1.0, 2.0, 0);
+
+        assert_eq!(pattern.next(), Some((BlinkyState::On, 250)));
+        assert_eq!(pattern.next(), Some((BlinkyState::Off, 250)));
+
+        let mut stalled = ChargingPattern::new(0.0, f32::NAN, 1);
+        let (_, duration) = stalled.next().unwrap();
+        assert_eq!(duration, u64::MAX / 2);
+    }
+
+    #[test]
+    fn test_blinky_controller_finish() {
+        let mut controller = BlinkyController::new(BlinkyConfig::default()).unwrap();
+        let mut led = RecordingLed::new();
+
+        controller.step(&mut led);
+        assert_eq!(controller.state(), BlinkyState::On);
+
+        controller.finish(&mut led);
+        assert_eq!(led.writes, vec![true, false]);
+        assert_eq!(controller.state(), BlinkyState::Off);
+    }
+
+    #[test]
+    fn test_blinky_controller_wait_next_on_async() {
+        let config = BlinkyConfig::new(100, 300).with_count(2);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = RecordingLed::new();
+        let mut delay = MockDelay::new();
+
+        // Already off: turns on immediately without waiting
+        assert_eq!(
+            block_on(controller.wait_next_on_async(&mut led, &mut delay)),
+            Ok(())
+        );
+        assert_eq!(led.writes, vec![true]);
+        assert!(delay.delays.is_empty());
+
+        // Currently on: switches off, waits the off duration, then turns on
+        assert_eq!(
+            block_on(controller.wait_next_on_async(&mut led, &mut delay)),
+            Ok(())
+        );
+        assert_eq!(led.writes, vec![true, false, true]);
+        assert_eq!(delay.delays, vec![300]);
+
+        // The final blink ends the pattern before another On
+        assert_eq!(
+            block_on(controller.wait_next_on_async(&mut led, &mut delay)),
+            Err(BlinkyError::PatternFinished)
+        );
+        assert_eq!(controller.state(), BlinkyState::Off);
+    }
+
+    #[test]
+    fn test_blinky_controller_status() {
+        let config = BlinkyConfig::new(100, 100).with_count(3);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = NullLed;
+
+        controller.step(&mut led);
+        controller.step(&mut led);
+        controller.step(&mut led);
+
+        assert_eq!(
+            controller.status(),
+            ControllerStatus {
+                state: BlinkyState::On,
+                blink_count: 1,
+                should_continue: true,
+                remaining: Some(2),
+            }
+        );
+
+        while controller.step(&mut led).is_some() {}
+        let status = controller.status();
+        assert!(!status.should_continue);
+        assert_eq!(status.remaining, Some(0));
+
+        let controller = BlinkyController::new(BlinkyConfig::default()).unwrap();
+        assert_eq!(controller.status().remaining, None);
+    }
+
+    #[test]
+    fn test_blinky_controller_run_until_count_async() {
+        let config = BlinkyConfig::new(100, 200).with_count(2);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = RecordingLed::new();
+        let mut delay = MockDelay::new();
+
+        // Runs past the configured count of 2
+        block_on(controller.run_until_count_async(&mut led, &mut delay, 4));
+        assert_eq!(controller.blink_count(), 4);
+        assert_eq!(controller.state(), BlinkyState::Off);
+        assert_eq!(led.writes.len(), 8);
+        assert_eq!(delay.delays, vec![100, 200, 100, 200, 100, 200, 100]);
+
+        // Already at the target: nothing happens
+        block_on(controller.run_until_count_async(&mut led, &mut delay, 3));
+        assert_eq!(led.writes.len(), 8);
+    }
+
+    #[test]
+    fn test_config_validator_counts_failures() {
+        let mut validator = ConfigValidator::new();
+
+        assert_eq!(validator.validate(&BlinkyConfig::default()), Ok(()));
+        assert_eq!(validator.failed_validations(), 0);
+
+        for expected in 1..=3 {
+            assert_eq!(
+                validator.validate(&BlinkyConfig::new(0, 0)),
+                Err(BlinkyError::InvalidConfig)
+            );
+            assert_eq!(validator.failed_validations(), expected);
+        }
+
+        let zero_count = BlinkyConfig::default().with_count(0);
+        assert!(validator.validate(&zero_count).is_err());
+        assert!(validator.validate(&BlinkyConfig::new(100, 100)).is_ok());
+        assert_eq!(validator.failed_validations(), 4);
+    }
+
+    #[test]
+    fn test_will_alias() {
+        let fast = BlinkyConfig::new(50, 50);
+        assert!(will_alias(&fast, 10.0));
+        assert!(will_alias(&fast, 19.0));
+        assert!(!will_alias(&fast, 20.0));
+        assert!(!will_alias(&fast, 1000.0));
+
+        assert!(will_alias(&fast, 0.0));
+        assert!(will_alias(&fast, f32::NAN));
+        assert!(!will_alias(&BlinkyConfig::new(0, 0), 10.0));
+    }
+
+    #[test]
+    fn test_common_period() {
+        let configs = [BlinkyConfig::new(200, 200), BlinkyConfig::new(100, 500)];
+        assert_eq!(common_period_ms(&configs), Some(1200));
+
+        let single = [BlinkyConfig::new(300, 400).with_count(2)];
+        assert_eq!(common_period_ms(&single), Some(700));
+
+        assert_eq!(common_period_ms(&[]), None);
+        assert_eq!(
+            common_period_ms(&[BlinkyConfig::new(100, 100), BlinkyConfig::new(0, 0)]),
+            None
+        );
+
+        let huge = [
+            BlinkyConfig::new(u64::MAX / 2, 0),
+            BlinkyConfig::new(u64::MAX / 2 - 1, 0),
+        ];
+        assert_eq!(common_period_ms(&huge), None);
+    }
+
+    #[test]
+    fn test_next_coincidence_in_phase() {
+        let config = BlinkyConfig::new(100, 100);
+        assert_eq!(next_coincidence_ms(&config, &config, 0), Some(0));
+        assert_eq!(next_coincidence_ms(&config, &config, 50), Some(50));
+    }
+
+    #[test]
+    fn test_next_coincidence_never() {
+        // Perfectly interleaved windows never overlap
+        let config = BlinkyConfig::new(100, 100);
+        assert_eq!(next_coincidence_ms(&config, &config, 100), None);
+
+        let dark = BlinkyConfig::new(0, 100);
+        assert_eq!(next_coincidence_ms(&config, &dark, 0), None);
+    }
+
+    #[test]
+    fn test_next_coincidence_near_max_durations() {
+        let a = BlinkyConfig::new(1, 2);
+        let b = BlinkyConfig::new(1, u64::MAX - 1);
+        assert_eq!(next_coincidence_ms(&a, &b, 9), Some(9));
+
+        // b's only window falls between a's, and the next is ~u64::MAX away
+        assert_eq!(next_coincidence_ms(&a, &b, 10), None);
+
+        // Windows that never line up stop at the search limit
+        let c = BlinkyConfig::new(1, 1);
+        let d = BlinkyConfig::new(1, 1_000_000_001);
+        assert_eq!(next_coincidence_ms(&c, &d, 11), None);
+
+        let huge = BlinkyConfig::new(u64::MAX / 2, u64::MAX / 2);
+        assert_eq!(next_coincidence_ms(&huge, &huge, u64::MAX - 1), Some(0));
+    }
+
+    #[test]
+    fn test_next_coincidence_different_periods() {
+        // a on at [0,100), [300,400); b on at [120,170), [320,370)
+        let a = BlinkyConfig::new(100, 200);
+        let b = BlinkyConfig::new(50, 150);
+        assert_eq!(next_coincidence_ms(&a, &b, 120), Some(320));
+
+        // b's window from the previous cycle is still on at time zero
+        let config = BlinkyConfig::new(100, 100);
+        assert_eq!(next_coincidence_ms(&config, &config, 150), Some(0));
+    }
+
+    #[test]
+    fn test_blinky_controller_manual_step() {
+        let config = BlinkyConfig::new(100, 200).with_count(2);
+        let mut controller = BlinkyController::new(config).unwrap();
+        let mut led = RecordingLed::new();
+
+        assert_eq!(
+            controller.manual_step(&mut led),
+            Some((BlinkyState::On, 100))
+        );
+        assert_eq!(
+            controller.manual_step(&mut led),
+            Some((BlinkyState::Off, 200))
+        );
+        assert_eq!(
+            controller.manual_step(&mut led),
+            Some((BlinkyState::On, 100))
+        );
+        assert_eq!(
+            controller.manual_step(&mut led),
+            Some((BlinkyState::Off, 200))
+        );
+        assert_eq!(controller.manual_step(&mut led), None);
+
+        assert_eq!(led.writes, vec![true, false, true, false]);
+    }
+
+    #[test]
+    fn test_blinky_controller_max_runtime() {
+        let config = BlinkyConfig::new(100, 100);
+        let mut controller = BlinkyController::new(config).unwrap();
+        controller.set_max_runtime_ms(Some(250));
+        let mut led = RecordingLed::new();
+
+        // Mock elapsed source: feed back each returned duration
+        let mut elapsed = 0;
+        while let Some(duration) = controller.step_with_elapsed(&mut led, elapsed) {
+            elapsed = duration;
+        }
+
+        assert_eq!(led.writes, vec![true, false, true, false]);
+        assert_eq!(controller.state(), BlinkyState::Off);
+        assert!(!controller.should_continue());
+        assert_eq!(controller.step(&mut led), None);
+
+        controller.rearm();
+        assert!(controller.should_continue());
+    }
+
+    #[test]
+    fn test_blinky_controller_max_runtime_when_off() {
+        let config = BlinkyConfig::new(100, 100);
+        let mut controller = BlinkyController::new(config).unwrap();
+        controller.set_max_runtime_ms(Some(150));
+        let mut led = RecordingLed::new();
+
+        assert_eq!(controller.step_with_elapsed(&mut led, 0), Some(100));
+        assert_eq!(controller.step_with_elapsed(&mut led, 100), Some(100));
+        assert_eq!(controller.step_with_elapsed(&mut led, 100), None);
+
+        // Already off, so no extra write is needed
+        assert_eq!(led.writes, vec![true, false]);
     }
 }
@@ -0,0 +1,77 @@
+//! Board support module
+//!
+//! This module keeps board-specific pin knowledge for the nRF52 DK
+//! boards in one place. It is only available with the `embassy` feature.
+
+use embassy_nrf::gpio::{Level, Output, OutputDrive};
+use embassy_nrf::Peripherals;
+
+/// User LEDs on the nRF52 DK boards
+///
+/// The LEDs are wired active-low, so `Level::Low` turns them on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum BoardLed {
+    Led1,
+    Led2,
+    Led3,
+    Led4,
+}
+
+impl BoardLed {
+    /// Every LED on the board, in label order
+    pub const ALL: [BoardLed; 4] = [
+        BoardLed::Led1,
+        BoardLed::Led2,
+        BoardLed::Led3,
+        BoardLed::Led4,
+    ];
+
+    /// Pin number of the LED on GPIO port 0
+    ///
+    /// This is the single source of the LED-to-pin mapping; `output`
+    /// selects its pin from it.
+    pub const fn pin_number(self) -> u8 {
+        match self {
+            BoardLed::Led1 => 13,
+            BoardLed::Led2 => 14,
+            BoardLed::Led3 => 15,
+            BoardLed::Led4 => 16,
+        }
+    }
+
+    /// Configure the LED's pin as a standard-drive output
+    ///
+    /// The pin is borrowed from `Peripherals`, so the HAL's ownership
+    /// checks still prevent it being used twice while the output is alive.
+    pub fn output(self, p: &mut Peripherals, initial_output: Level) -> Output<'_> {
+        let drive = OutputDrive::Standard;
+        match self.pin_number() {
+            13 => Output::new(&mut p.P0_13, initial_output, drive),
+            14 => Output::new(&mut p.P0_14, initial_output, drive),
+            15 => Output::new(&mut p.P0_15, initial_output, drive),
+            16 => Output::new(&mut p.P0_16, initial_output, drive),
+            // Rejected at compile time by the pin table check below
+            _ => unreachable!(),
+        }
+    }
+}
+
+// Compile-time test: every LED maps to its expected pin, the pins are
+// distinct, and each is one that `output` can drive
+const _: () = {
+    const EXPECTED: [u8; 4] = [13, 14, 15, 16];
+
+    let mut i = 0;
+    while i < BoardLed::ALL.len() {
+        let pin = BoardLed::ALL[i].pin_number();
+        assert!(pin == EXPECTED[i], "LED mapped to the wrong pin");
+        assert!(pin >= 13 && pin <= 16, "LED pin not supported by output");
+
+        let mut j = i + 1;
+        while j < BoardLed::ALL.len() {
+            assert!(pin != BoardLed::ALL[j].pin_number(), "LEDs share a pin");
+            j += 1;
+        }
+        i += 1;
+    }
+};
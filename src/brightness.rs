@@ -0,0 +1,463 @@
+//! Brightness pattern module
+//!
+//! Patterns in this module emit PWM brightness levels (0-255) rather
+//! than plain on/off states.
+
+use crate::blinky::AsyncDelay;
+use crate::{xorshift32, BlinkyPattern, BlinkyState};
+
+/// LED driver that supports PWM brightness
+pub trait BrightnessLed {
+    /// Set the brightness (0 = off, 255 = full)
+    fn set_brightness(&mut self, level: u8);
+}
+
+/// Brightness for a second LED driven in opposition to the first
+pub fn complement_brightness(level: u8) -> u8 {
+    255 - level
+}
+
+/// Snap a full-range brightness to one of `levels` evenly spaced outputs
+///
+/// The result is scaled back to 0-255 for the driver, so 0 and 255 are
+/// always preserved. Fewer than two levels cannot be spaced, so the level
+/// is returned unchanged.
+pub fn quantize_brightness(level: u8, levels: u8) -> u8 {
+    if levels < 2 {
+        return level;
+    }
+
+    let steps = (levels - 1) as u32;
+    let index = (level as u32 * steps + 127) / 255;
+    ((index * 255 + steps / 2) / steps) as u8
+}
+
+/// Linearly ramp an LED from `from` to `to` over `duration_ms`
+///
+/// Writes `from` immediately, then `steps` further levels ending exactly at
+/// `to`, awaiting an equal share of the duration before each. The shares
+/// differ by at most 1ms so they sum to `duration_ms`. Every share is at
+/// least 1ms: `steps` is reduced to at most `duration_ms`, zero steps is
+/// treated as one, and a zero duration waits 1ms.
+pub async fn fade<L: BrightnessLed, D: AsyncDelay>(
+    led: &mut L,
+    delay: &mut D,
+    from: u8,
+    to: u8,
+    duration_ms: u64,
+    steps: u16,
+) {
+    let steps = (steps as u64).clamp(1, duration_ms.max(1));
+    let span = to as i64 - from as i64;
+    led.set_brightness(from);
+
+    // Split in u128 so long durations cannot overflow
+    let share_end = |step: u64| (duration_ms as u128 * step as u128 / steps as u128) as u64;
+
+    for step in 1..=steps {
+        delay
+            .delay_ms((share_end(step) - share_end(step - 1)).max(1))
+            .await;
+
+        let level = from as i64 + span * step as i64 / steps as i64;
+        led.set_brightness(level as u8);
+    }
+}
+
+/// A pattern that produces a stream of brightness levels
+pub trait BrightnessSource {
+    /// Advance the pattern
+    /// Returns the brightness and how long to hold it
+    fn next_level(&mut self) -> (u8, u64);
+}
+
+/// Two LEDs breathing in opposition
+///
+/// The first LED ramps up and down between 0 and 255 in steps of
+/// `step_size`; the second always shows the complement, so one fades in
+/// as the other fades out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DualBreathingPattern {
+    step_size: u8,
+    step_duration_ms: u64,
+    level: u8,
+    rising: bool,
+}
+
+impl DualBreathingPattern {
+    /// Create a pattern starting with the first LED fully off
+    ///
+    /// A `step_size` of zero is treated as 1.
+    pub fn new(step_size: u8, step_duration_ms: u64) -> Self {
+        Self {
+            step_size: step_size.max(1),
+            step_duration_ms,
+            level: 0,
+            rising: true,
+        }
+    }
+
+    /// Emit the current levels and advance the ramp
+    /// Returns the first LED's brightness, the second's, and how long to hold them
+    fn advance(&mut self) -> (u8, u8, u64) {
+        let level = self.level;
+
+        if self.rising {
+            self.level = level.saturating_add(self.step_size);
+            self.rising = self.level < 255;
+        } else {
+            self.level = level.saturating_sub(self.step_size);
+            self.rising = self.level == 0;
+        }
+
+        (level, complement_brightness(level), self.step_duration_ms)
+    }
+}
+
+impl Iterator for DualBreathingPattern {
+    type Item = (u8, u8, u64);
+
+    /// Never returns `None`; the pattern runs forever
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.advance())
+    }
+}
+
+/// Candle-like flicker that random-walks brightness within bounds
+///
+/// Each step moves the brightness by a random amount of at most
+/// `max_delta`, clamped to `[min, max]`. The walk is driven by a seeded
+/// PRNG, so a fixed seed always reproduces the same sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlamePattern {
+    min: u8,
+    max: u8,
+    max_delta: u8,
+    step_duration_ms: u64,
+    level: u8,
+    rng_state: u32,
+}
+
+impl FlamePattern {
+    /// Create a flame starting midway between `min` and `max`
+    ///
+    /// The bounds are swapped if given in the wrong order. A zero seed is
+    /// replaced with a fixed nonzero value since xorshift cannot leave zero.
+    pub fn new(min: u8, max: u8, max_delta: u8, step_duration_ms: u64, seed: u32) -> Self {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        Self {
+            min,
+            max,
+            max_delta,
+            step_duration_ms,
+            level: min + (max - min) / 2,
+            rng_state: if seed == 0 { 0x9E37_79B9 } else { seed },
+        }
+    }
+
+    /// Get the current brightness
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Take one random step
+    /// Returns the new brightness and how long to hold it
+    fn advance(&mut self) -> (u8, u64) {
+        let span = self.max_delta as i32 * 2 + 1;
+        let delta = (xorshift32(&mut self.rng_state) % span as u32) as i32 - self.max_delta as i32;
+        let level = (self.level as i32 + delta).clamp(self.min as i32, self.max as i32);
+
+        self.level = level as u8;
+        (self.level, self.step_duration_ms)
+    }
+}
+
+impl Iterator for FlamePattern {
+    type Item = (u8, u64);
+
+    /// Never returns `None`; the pattern runs forever
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.advance())
+    }
+}
+
+impl BrightnessSource for DualBreathingPattern {
+    /// Uses the first LED's brightness
+    fn next_level(&mut self) -> (u8, u64) {
+        let (level, _, duration_ms) = self.advance();
+        (level, duration_ms)
+    }
+}
+
+impl BrightnessSource for FlamePattern {
+    fn next_level(&mut self) -> (u8, u64) {
+        self.advance()
+    }
+}
+
+/// Brightness pattern switched on and off by a slow blink gate
+///
+/// While the gate is on, levels come from the source; while it is off the
+/// output is forced to 0. Source steps are cut short at gate boundaries
+/// so the gate timing is kept exactly. Every emitted step lasts at least
+/// 1ms: zero-length source steps are stretched to 1ms, and a gate with
+/// both durations zero never opens and emits 1ms dark steps.
+pub struct GatedBrightnessPattern<S> {
+    source: S,
+    gate: BlinkyPattern,
+    gate_state: BlinkyState,
+    gate_remaining_ms: u64,
+}
+
+impl<S: BrightnessSource> GatedBrightnessPattern<S> {
+    /// Create a pattern gating `source` with `gate`
+    pub fn new(source: S, gate: BlinkyPattern) -> Self {
+        Self {
+            source,
+            gate,
+            gate_state: BlinkyState::Off,
+            gate_remaining_ms: 0,
+        }
+    }
+
+    /// Emit the next brightness step
+    /// Returns the brightness and how long to hold it
+    fn advance(&mut self) -> (u8, u64) {
+        // A zero-length window is skipped; two in a row means the gate is empty
+        for _ in 0..2 {
+            if self.gate_remaining_ms > 0 {
+                break;
+            }
+            let (state, duration_ms) = self.gate.next();
+            self.gate_state = state;
+            self.gate_remaining_ms = duration_ms as u64;
+        }
+        if self.gate_remaining_ms == 0 {
+            return (0, 1);
+        }
+
+        let (level, duration_ms) = match self.gate_state {
+            BlinkyState::On => {
+                let (level, duration_ms) = self.source.next_level();
+                (level, duration_ms.clamp(1, self.gate_remaining_ms))
+            }
+            BlinkyState::Off => (0, self.gate_remaining_ms),
+        };
+
+        self.gate_remaining_ms -= duration_ms;
+        (level, duration_ms)
+    }
+}
+
+impl<S: BrightnessSource> Iterator for GatedBrightnessPattern<S> {
+    type Item = (u8, u64);
+
+    /// Never returns `None`; the pattern runs forever
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.advance())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{block_on, MockDelay};
+
+    /// LED that records every brightness written
+    struct RecordingBrightnessLed {
+        levels: Vec<u8>,
+    }
+
+    impl BrightnessLed for RecordingBrightnessLed {
+        fn set_brightness(&mut self, level: u8) {
+            self.levels.push(level);
+        }
+    }
+
+    #[test]
+    fn test_complement_brightness() {
+        assert_eq!(complement_brightness(0), 255);
+        assert_eq!(complement_brightness(255), 0);
+        assert_eq!(complement_brightness(100), 155);
+    }
+
+    #[test]
+    fn test_quantize_brightness() {
+        assert_eq!(quantize_brightness(0, 4), 0);
+        assert_eq!(quantize_brightness(255, 4), 255);
+        assert_eq!(quantize_brightness(40, 4), 0);
+        assert_eq!(quantize_brightness(50, 4), 85);
+        assert_eq!(quantize_brightness(100, 4), 85);
+        assert_eq!(quantize_brightness(128, 4), 170);
+        assert_eq!(quantize_brightness(220, 4), 255);
+
+        assert_eq!(quantize_brightness(255, 16), 255);
+        assert_eq!(quantize_brightness(17, 16), 17);
+
+        assert_eq!(quantize_brightness(123, 1), 123);
+        assert_eq!(quantize_brightness(123, 0), 123);
+    }
+
+    #[test]
+    fn test_dual_breathing_sums_to_full() {
+        let mut pattern = DualBreathingPattern::new(40, 20);
+        let mut levels = Vec::new();
+
+        for _ in 0..30 {
+            let (first, second, duration) = pattern.next().unwrap();
+            assert_eq!(first as u16 + second as u16, 255);
+            assert_eq!(duration, 20);
+            levels.push(first);
+        }
+
+        assert_eq!(&levels[..9], &[0, 40, 80, 120, 160, 200, 240, 255, 215]);
+        assert!(levels.contains(&0));
+    }
+
+    #[test]
+    fn test_flame_pattern_stays_in_bounds() {
+        let mut flame = FlamePattern::new(80, 200, 40, 30, 12345);
+
+        for _ in 0..1000 {
+            let (level, duration) = flame.next().unwrap();
+            assert!((80..=200).contains(&level));
+            assert_eq!(duration, 30);
+        }
+    }
+
+    #[test]
+    fn test_flame_pattern_is_reproducible() {
+        let mut a = FlamePattern::new(0, 255, 20, 50, 42);
+        let mut b = FlamePattern::new(0, 255, 20, 50, 42);
+
+        for _ in 0..100 {
+            assert_eq!(a.next(), b.next());
+        }
+    }
+
+    #[test]
+    fn test_flame_pattern_moves() {
+        let mut flame = FlamePattern::new(0, 255, 10, 50, 7);
+        let start = flame.level();
+
+        let moved = (0..100).any(|_| flame.next().unwrap().0 != start);
+        assert!(moved);
+    }
+
+    #[test]
+    fn test_gated_brightness_follows_gate() {
+        let source = DualBreathingPattern::new(40, 20);
+        let gate = BlinkyPattern::new(crate::BlinkyConfig::new(100, 60));
+        let mut pattern = GatedBrightnessPattern::new(source, gate);
+
+        let steps: Vec<_> = pattern.by_ref().take(11).collect();
+
+        assert_eq!(
+            steps,
+            vec![
+                (0, 20),
+                (40, 20),
+                (80, 20),
+                (120, 20),
+                (160, 20),
+                (0, 60),
+                (200, 20),
+                (240, 20),
+                (255, 20),
+                (215, 20),
+                (175, 20),
+            ]
+        );
+        assert_eq!(pattern.next(), Some((0, 60)));
+    }
+
+    #[test]
+    fn test_gated_brightness_truncates_at_gate_edge() {
+        let source = FlamePattern::new(100, 200, 10, 70, 1);
+        let gate = BlinkyPattern::new(crate::BlinkyConfig::new(100, 50));
+        let mut pattern = GatedBrightnessPattern::new(source, gate);
+
+        let (level, duration) = pattern.next().unwrap();
+        assert!((100..=200).contains(&level));
+        assert_eq!(duration, 70);
+
+        let (_, duration) = pattern.next().unwrap();
+        assert_eq!(duration, 30);
+        assert_eq!(pattern.next(), Some((0, 50)));
+    }
+
+    #[test]
+    fn test_fade_ramps_up() {
+        let mut led = RecordingBrightnessLed { levels: Vec::new() };
+        let mut delay = MockDelay::new();
+
+        block_on(fade(&mut led, &mut delay, 10, 250, 1000, 7));
+
+        assert_eq!(led.levels.first(), Some(&10));
+        assert_eq!(led.levels.last(), Some(&250));
+        assert_eq!(led.levels.len(), 8);
+        assert!(led.levels.windows(2).all(|w| w[0] <= w[1]));
+
+        assert_eq!(delay.delays.len(), 7);
+        assert_eq!(delay.delays.iter().sum::<u64>(), 1000);
+        assert!(delay.delays.iter().all(|&ms| ms > 0));
+    }
+
+    #[test]
+    fn test_fade_ramps_down() {
+        let mut led = RecordingBrightnessLed { levels: Vec::new() };
+        let mut delay = MockDelay::new();
+
+        block_on(fade(&mut led, &mut delay, 255, 0, 300, 3));
+
+        assert_eq!(led.levels, vec![255, 170, 85, 0]);
+        assert_eq!(delay.delays, vec![100, 100, 100]);
+    }
+
+    #[test]
+    fn test_fade_long_duration() {
+        let mut led = RecordingBrightnessLed { levels: Vec::new() };
+        let mut delay = MockDelay::new();
+
+        block_on(fade(&mut led, &mut delay, 0, 255, u64::MAX, u16::MAX));
+
+        assert_eq!(led.levels.last(), Some(&255));
+        assert_eq!(delay.delays.len(), u16::MAX as usize);
+        assert_eq!(
+            delay.delays.iter().map(|&ms| ms as u128).sum::<u128>(),
+            u64::MAX as u128
+        );
+    }
+
+    #[test]
+    fn test_fade_more_steps_than_milliseconds() {
+        let mut led = RecordingBrightnessLed { levels: Vec::new() };
+        let mut delay = MockDelay::new();
+
+        block_on(fade(&mut led, &mut delay, 0, 255, 5, 200));
+
+        assert_eq!(delay.delays, vec![1; 5]);
+        assert_eq!(led.levels, vec![0, 51, 102, 153, 204, 255]);
+
+        let mut delay = MockDelay::new();
+        block_on(fade(&mut led, &mut delay, 0, 255, 0, 10));
+        assert_eq!(delay.delays, vec![1]);
+        assert!(delay.delays.iter().all(|&ms| ms > 0));
+    }
+
+    #[test]
+    fn test_gated_brightness_never_emits_zero_duration() {
+        let empty_gate = BlinkyPattern::new(crate::BlinkyConfig::new(0, 0));
+        let mut pattern =
+            GatedBrightnessPattern::new(DualBreathingPattern::new(40, 20), empty_gate);
+        for _ in 0..10 {
+            assert_eq!(pattern.next(), Some((0, 1)));
+        }
+
+        // A source with zero-length steps still advances real time
+        let gate = BlinkyPattern::new(crate::BlinkyConfig::new(3, 2));
+        let mut pattern = GatedBrightnessPattern::new(DualBreathingPattern::new(40, 0), gate);
+        let steps: Vec<_> = pattern.by_ref().take(5).collect();
+        assert_eq!(steps, vec![(0, 1), (40, 1), (80, 1), (0, 2), (120, 1)]);
+    }
+}
@@ -0,0 +1,181 @@
+//! Multi-LED pattern module
+//!
+//! Patterns in this module drive a fixed number of LEDs at once,
+//! emitting one on/off frame per step.
+
+/// Incrementing Gray-code counter across `N` LEDs
+///
+/// Only one LED changes per step. LED `i` shows bit `i` of the code, and
+/// the counter wraps after `2^N` states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrayCounterPattern<const N: usize> {
+    counter: u32,
+    step_duration_ms: u64,
+}
+
+impl<const N: usize> GrayCounterPattern<N> {
+    /// Create a counter starting at zero
+    pub fn new(step_duration_ms: u64) -> Self {
+        Self {
+            counter: 0,
+            step_duration_ms,
+        }
+    }
+
+    /// Mask limiting the counter to `N` bits
+    fn mask() -> u32 {
+        if N >= 32 {
+            u32::MAX
+        } else {
+            (1 << N) - 1
+        }
+    }
+
+    /// Current Gray code value
+    pub fn code(&self) -> u32 {
+        self.counter ^ (self.counter >> 1)
+    }
+
+    /// Emit the current frame and advance the counter
+    /// Returns the LED states and how long to show them
+    fn advance(&mut self) -> ([bool; N], u64) {
+        let code = self.code();
+        let mut frame = [false; N];
+        for (bit, led) in frame.iter_mut().enumerate().take(32) {
+            *led = code & (1 << bit) != 0;
+        }
+
+        self.counter = self.counter.wrapping_add(1) & Self::mask();
+        (frame, self.step_duration_ms)
+    }
+}
+
+impl<const N: usize> Iterator for GrayCounterPattern<N> {
+    type Item = ([bool; N], u64);
+
+    /// Never returns `None`; the pattern runs forever
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.advance())
+    }
+}
+
+/// "Knight rider" scanner bouncing a single lit LED across `N` LEDs
+///
+/// The lit index moves 0 to N-1 and back again, reversing at each end
+/// without repeating the end LEDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BouncePattern<const N: usize> {
+    index: usize,
+    forward: bool,
+    step_duration_ms: u64,
+}
+
+impl<const N: usize> BouncePattern<N> {
+    /// Create a scanner starting at LED 0
+    pub fn new(step_duration_ms: u64) -> Self {
+        Self {
+            index: 0,
+            forward: true,
+            step_duration_ms,
+        }
+    }
+
+    /// Index of the LED lit by the next frame
+    pub fn lit_index(&self) -> usize {
+        self.index
+    }
+
+    /// Emit the current frame and move the lit LED
+    /// Returns the LED states and how long to show them
+    fn advance(&mut self) -> ([bool; N], u64) {
+        let mut frame = [false; N];
+        if N == 0 {
+            return (frame, self.step_duration_ms);
+        }
+        frame[self.index] = true;
+
+        if N > 1 {
+            if self.forward && self.index == N - 1 {
+                self.forward = false;
+            } else if !self.forward && self.index == 0 {
+                self.forward = true;
+            }
+            if self.forward {
+                self.index += 1;
+            } else {
+                self.index -= 1;
+            }
+        }
+
+        (frame, self.step_duration_ms)
+    }
+}
+
+impl<const N: usize> Iterator for BouncePattern<N> {
+    type Item = ([bool; N], u64);
+
+    /// Never returns `None`; the pattern runs forever
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.advance())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gray_counter_two_leds() {
+        let mut pattern = GrayCounterPattern::<2>::new(100);
+
+        // Codes 00, 01, 11, 10 with LED 0 as the low bit
+        assert_eq!(pattern.next(), Some(([false, false], 100)));
+        assert_eq!(pattern.next(), Some(([true, false], 100)));
+        assert_eq!(pattern.next(), Some(([true, true], 100)));
+        assert_eq!(pattern.next(), Some(([false, true], 100)));
+
+        // Wraps back to 00
+        assert_eq!(pattern.next(), Some(([false, false], 100)));
+        assert_eq!(pattern.next(), Some(([true, false], 100)));
+    }
+
+    #[test]
+    fn test_gray_counter_single_bit_changes() {
+        let mut pattern = GrayCounterPattern::<4>::new(50);
+        let (mut previous, _) = pattern.next().unwrap();
+
+        for _ in 0..32 {
+            let (frame, _) = pattern.next().unwrap();
+            let changed = frame
+                .iter()
+                .zip(previous.iter())
+                .filter(|(a, b)| a != b)
+                .count();
+            assert_eq!(changed, 1);
+            previous = frame;
+        }
+    }
+
+    #[test]
+    fn test_bounce_pattern_three_leds() {
+        let mut pattern = BouncePattern::<3>::new(80);
+        let mut lit = Vec::new();
+
+        for _ in 0..9 {
+            let (frame, duration) = pattern.next().unwrap();
+            assert_eq!(duration, 80);
+            assert_eq!(frame.iter().filter(|&&on| on).count(), 1);
+            lit.push(frame.iter().position(|&on| on).unwrap());
+        }
+
+        assert_eq!(lit, vec![0, 1, 2, 1, 0, 1, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_bounce_pattern_single_led() {
+        let mut pattern = BouncePattern::<1>::new(80);
+        for _ in 0..3 {
+            assert_eq!(pattern.next(), Some(([true], 80)));
+        }
+    }
+}
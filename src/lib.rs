@@ -3,9 +3,42 @@
 //! This module contains the core application logic that can be tested
 //! without requiring actual hardware.
 
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+pub mod blinky;
+#[cfg(feature = "embassy")]
+pub mod board;
+pub mod brightness;
+pub mod chase;
+#[cfg(test)]
+mod test_support;
 
 use core::fmt;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// Errors produced when building or validating blinky configurations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlinkyError {
+    /// A duration was zero
+    ZeroDuration,
+    /// A configuration failed validation
+    InvalidConfig,
+    /// A finite pattern ran out of blinks
+    PatternFinished,
+    /// A label did not fit in its fixed-capacity buffer
+    NameTooLong,
+}
+
+impl fmt::Display for BlinkyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlinkyError::ZeroDuration => write!(f, "duration must be nonzero"),
+            BlinkyError::InvalidConfig => write!(f, "invalid configuration"),
+            BlinkyError::PatternFinished => write!(f, "pattern finished"),
+            BlinkyError::NameTooLong => write!(f, "name too long"),
+        }
+    }
+}
 
 /// Blinky pattern state machine
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,6 +68,14 @@ impl BlinkyState {
     pub fn as_bool(&self) -> bool {
         matches!(self, BlinkyState::On)
     }
+
+    /// Every `(from, to)` transition of the state machine
+    pub const fn transition_table() -> [(BlinkyState, BlinkyState); 2] {
+        [
+            (BlinkyState::Off, BlinkyState::On),
+            (BlinkyState::On, BlinkyState::Off),
+        ]
+    }
 }
 
 impl fmt::Display for BlinkyState {
@@ -46,6 +87,139 @@ impl fmt::Display for BlinkyState {
     }
 }
 
+/// Direction of a state transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    /// Off to On
+    Rising,
+    /// On to Off
+    Falling,
+}
+
+/// Count positions where two recorded state sequences differ
+///
+/// Extra entries in the longer sequence each count as a mismatch.
+pub fn sequence_distance(a: &[BlinkyState], b: &[BlinkyState]) -> usize {
+    let mismatches = a.iter().zip(b).filter(|(x, y)| x != y).count();
+    mismatches + a.len().abs_diff(b.len())
+}
+
+/// Effective blink frequency in Hz from a measured half-period
+///
+/// Assumes a symmetric pattern where the half-period is one state's
+/// duration. Returns 0.0 for a zero half-period.
+pub fn frequency_from_half_period_ms(half_ms: u32) -> f32 {
+    match half_ms {
+        0 => 0.0,
+        _ => 1000.0 / (2.0 * half_ms as f32),
+    }
+}
+
+/// Compress sampled states into `(value, run_length)` pairs
+///
+/// Runs longer than `u16::MAX` are split across several pairs. If there
+/// are more than `N` runs the output is truncated after the first `N`.
+pub fn run_length_encode<const N: usize>(states: &[bool]) -> heapless::Vec<(bool, u16), N> {
+    let mut runs = heapless::Vec::new();
+    let mut current: Option<(bool, u16)> = None;
+
+    for &state in states {
+        current = match current {
+            Some((value, length)) if value == state && length < u16::MAX => {
+                Some((value, length + 1))
+            }
+            Some(run) => {
+                if runs.push(run).is_err() {
+                    return runs;
+                }
+                Some((state, 1))
+            }
+            None => Some((state, 1)),
+        };
+    }
+
+    if let Some(run) = current {
+        let _ = runs.push(run);
+    }
+    runs
+}
+
+/// Advance a xorshift32 generator and return the new value
+pub(crate) fn xorshift32(state: &mut u32) -> u32 {
+    let mut x = *state;
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    *state = x;
+    x
+}
+
+/// Generate a random valid config for property tests
+///
+/// Both durations fall in `1..=10000` ms. The generator state is advanced
+/// in place, so a fixed seed reproduces the same sequence of configs. A
+/// zero state is replaced with a fixed nonzero value since xorshift cannot
+/// leave zero.
+#[cfg(any(test, feature = "test-util"))]
+pub fn random_valid_config(rng_state: &mut u32) -> BlinkyConfig {
+    if *rng_state == 0 {
+        *rng_state = 0x9E37_79B9;
+    }
+    let on_duration_ms = xorshift32(rng_state) % 10_000 + 1;
+    let off_duration_ms = xorshift32(rng_state) % 10_000 + 1;
+    BlinkyConfig::new(on_duration_ms, off_duration_ms)
+}
+
+/// Lock-free cell holding a `BlinkyState`
+///
+/// Lets the main loop publish the current state while an interrupt handler
+/// reads it without a mutex.
+#[derive(Debug)]
+pub struct AtomicBlinkyState {
+    value: AtomicU8,
+}
+
+impl AtomicBlinkyState {
+    const OFF: u8 = 0;
+    const ON: u8 = 1;
+
+    /// Create a new atomic state
+    pub const fn new(state: BlinkyState) -> Self {
+        Self {
+            value: AtomicU8::new(Self::encode(state)),
+        }
+    }
+
+    const fn encode(state: BlinkyState) -> u8 {
+        match state {
+            BlinkyState::On => Self::ON,
+            BlinkyState::Off => Self::OFF,
+        }
+    }
+
+    /// Read the current state
+    pub fn load(&self) -> BlinkyState {
+        match self.value.load(Ordering::Acquire) {
+            Self::ON => BlinkyState::On,
+            _ => BlinkyState::Off,
+        }
+    }
+
+    /// Publish a new state
+    pub fn store(&self, state: BlinkyState) {
+        self.value.store(Self::encode(state), Ordering::Release);
+    }
+}
+
+/// Device conditions with a conventional blink pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceStatus {
+    Error,
+    Warning,
+    Ok,
+    Connecting,
+}
+
 /// Blinky pattern configuration
 #[derive(Debug, Clone, Copy)]
 pub struct BlinkyConfig {
@@ -73,6 +247,23 @@ impl BlinkyConfig {
         }
     }
 
+    /// Create a configuration, rejecting zero durations during const evaluation
+    ///
+    /// Used in a `const` item, a zero duration becomes a compile error:
+    ///
+    /// ```compile_fail
+    /// use active_note::BlinkyConfig;
+    ///
+    /// const BROKEN: BlinkyConfig = BlinkyConfig::new_checked(0, 500);
+    /// ```
+    ///
+    /// Outside const context the same check panics at runtime.
+    pub const fn new_checked(on_duration_ms: u32, off_duration_ms: u32) -> Self {
+        assert!(on_duration_ms > 0, "on duration must be nonzero");
+        assert!(off_duration_ms > 0, "off duration must be nonzero");
+        Self::new(on_duration_ms, off_duration_ms)
+    }
+
     /// Create a fast blink pattern
     pub const fn fast() -> Self {
         Self::new(100, 100)
@@ -83,6 +274,110 @@ impl BlinkyConfig {
         Self::new(1000, 1000)
     }
 
+    /// Create a long, even breathing-style pattern
+    pub const fn breathing() -> Self {
+        Self::new(2000, 2000)
+    }
+
+    /// Create a short pulse followed by a long pause
+    pub const fn heartbeat() -> Self {
+        Self::new(100, 900)
+    }
+
+    /// Get the conventional pattern for a device status
+    ///
+    /// Error blinks fast, warning at a medium rate and ok is a slow
+    /// heartbeat. Connecting is a double blink, which a single on/off pair
+    /// cannot express: this returns the timing of each flash in the pair,
+    /// and `blinky::RestingPattern::for_status` adds the rest between pairs.
+    pub const fn for_status(status: DeviceStatus) -> Self {
+        match status {
+            DeviceStatus::Error => Self::fast(),
+            DeviceStatus::Warning => Self::new(250, 250),
+            DeviceStatus::Ok => Self::heartbeat(),
+            DeviceStatus::Connecting => Self::new(60, 140),
+        }
+    }
+
+    /// Built-in presets in menu order
+    pub const PRESETS: &'static [(&'static str, BlinkyConfig)] = &[
+        ("default", Self::new(500, 500)),
+        ("fast", Self::fast()),
+        ("slow", Self::slow()),
+        ("breathing", Self::breathing()),
+        ("heartbeat", Self::heartbeat()),
+    ];
+
+    /// Get the preset following this one, wrapping at the end
+    ///
+    /// Presets are matched by durations. A config that matches no preset
+    /// returns the first one.
+    pub fn next_preset(&self) -> BlinkyConfig {
+        let position = Self::PRESETS.iter().position(|(_, preset)| {
+            preset.on_duration_ms == self.on_duration_ms
+                && preset.off_duration_ms == self.off_duration_ms
+        });
+
+        match position {
+            Some(index) => Self::PRESETS[(index + 1) % Self::PRESETS.len()].1,
+            None => Self::PRESETS[0].1,
+        }
+    }
+
+    /// Get the `(on, off)` durations
+    pub fn durations(&self) -> (u32, u32) {
+        (self.on_duration_ms, self.off_duration_ms)
+    }
+
+    /// Create a config where one blink cycle lasts one beat
+    ///
+    /// The period is `60000 / bpm` ms, with `duty` (strictly between 0.0
+    /// and 1.0) of it spent on. Durations are rounded to the nearest
+    /// millisecond; one that rounds to zero is rejected.
+    pub fn from_bpm(bpm: f32, duty: f32) -> Result<BlinkyConfig, BlinkyError> {
+        if !(bpm > 0.0 && duty > 0.0 && duty < 1.0) {
+            return Err(BlinkyError::InvalidConfig);
+        }
+
+        // Float-to-int casts saturate, so extreme tempos cannot wrap
+        let period_ms = (60_000.0 / bpm + 0.5) as u32;
+        let on_duration_ms = (period_ms as f32 * duty + 0.5) as u32;
+        let config = Self::new(on_duration_ms, period_ms.saturating_sub(on_duration_ms));
+
+        if config.is_valid() {
+            Ok(config)
+        } else {
+            Err(BlinkyError::ZeroDuration)
+        }
+    }
+
+    /// Get the tempo in beats per minute, treating one cycle as one beat
+    ///
+    /// Returns 0.0 for a zero period.
+    pub fn bpm(&self) -> f32 {
+        match self.on_duration_ms as u64 + self.off_duration_ms as u64 {
+            0 => 0.0,
+            period_ms => 60_000.0 / period_ms as f32,
+        }
+    }
+
+    /// Get the on and off fractions of the period, independent of speed
+    ///
+    /// The fractions sum to 1.0, so configs with the same duty normalize
+    /// identically. Returns `(0.0, 0.0)` for a zero period.
+    pub fn normalized(&self) -> (f32, f32) {
+        match self.on_duration_ms as u64 + self.off_duration_ms as u64 {
+            0 => (0.0, 0.0),
+            period_ms => {
+                let period_ms = period_ms as f32;
+                (
+                    self.on_duration_ms as f32 / period_ms,
+                    self.off_duration_ms as f32 / period_ms,
+                )
+            }
+        }
+    }
+
     /// Get duration for current state
     pub fn duration_for_state(&self, state: BlinkyState) -> u32 {
         match state {
@@ -91,10 +386,224 @@ impl BlinkyConfig {
         }
     }
 
+    /// Get the LED state `t_ms` after the pattern starts with an on phase
+    ///
+    /// A zero period is always off.
+    pub fn state_at(&self, t_ms: u64) -> BlinkyState {
+        match self.on_duration_ms as u64 + self.off_duration_ms as u64 {
+            0 => BlinkyState::Off,
+            period_ms if t_ms % period_ms < self.on_duration_ms as u64 => BlinkyState::On,
+            _ => BlinkyState::Off,
+        }
+    }
+
+    /// Ratio between the states beyond which the LED no longer looks blinking
+    pub const SOLID_RATIO: u64 = 50;
+
+    /// Detect configs that will look solidly on or off rather than blinking
+    ///
+    /// Returns `Some(On)` when the on duration is at least `SOLID_RATIO`
+    /// times the off duration, `Some(Off)` in the inverse case, and `None`
+    /// for a visible blink.
+    pub fn perceived_state(&self) -> Option<BlinkyState> {
+        let on = self.on_duration_ms as u64;
+        let off = self.off_duration_ms as u64;
+
+        if on >= off * Self::SOLID_RATIO && on > 0 {
+            Some(BlinkyState::On)
+        } else if off >= on * Self::SOLID_RATIO && off > 0 {
+            Some(BlinkyState::Off)
+        } else {
+            None
+        }
+    }
+
     /// Validate configuration
     pub fn is_valid(&self) -> bool {
         self.on_duration_ms > 0 && self.off_duration_ms > 0
     }
+
+    /// Round both durations to a whole number of timer ticks at `tick_hz`
+    ///
+    /// Each duration becomes the nearest nonzero tick count, converted back
+    /// to the nearest millisecond (at least 1ms). A `tick_hz` of zero
+    /// returns the config unchanged.
+    pub fn round_to_tick(&self, tick_hz: u32) -> BlinkyConfig {
+        if tick_hz == 0 {
+            return *self;
+        }
+
+        let tick_hz = tick_hz as u64;
+        let round = |duration_ms: u32| {
+            let ticks = ((duration_ms as u64 * tick_hz + 500) / 1000).max(1);
+            let rounded_ms = (ticks * 1000 + tick_hz / 2) / tick_hz;
+            rounded_ms.clamp(1, u32::MAX as u64) as u32
+        };
+
+        Self::new(round(self.on_duration_ms), round(self.off_duration_ms))
+    }
+
+    /// Sample one period of the pattern into a fixed-size frame
+    ///
+    /// Sample `i` is taken at `i * sample_ms` and is `true` while the LED is
+    /// on. If one period needs more than `N` samples the frame is truncated;
+    /// samples past the end of the period are `false`. A `sample_ms` of zero
+    /// yields an all-`false` frame.
+    pub fn to_frame<const N: usize>(&self, sample_ms: u32) -> [bool; N] {
+        let mut frame = [false; N];
+        if sample_ms == 0 {
+            return frame;
+        }
+
+        let period = self.on_duration_ms as u64 + self.off_duration_ms as u64;
+        for (i, sample) in frame.iter_mut().enumerate() {
+            let t = i as u64 * sample_ms as u64;
+            if t >= period {
+                break;
+            }
+            *sample = t < self.on_duration_ms as u64;
+        }
+        frame
+    }
+
+    /// Number of samples `to_frame` needs to cover one full period
+    ///
+    /// Rounds up so the last partial sample is included. A `sample_ms` of
+    /// zero returns 0.
+    pub fn samples_per_period(&self, sample_ms: u32) -> u32 {
+        if sample_ms == 0 {
+            return 0;
+        }
+
+        let period = self.on_duration_ms as u64 + self.off_duration_ms as u64;
+        u32::try_from(period.div_ceil(sample_ms as u64)).unwrap_or(u32::MAX)
+    }
+
+    /// Convert to the controller config used by the `blinky` module
+    pub fn to_controller_config(&self, count: Option<u32>) -> blinky::BlinkyConfig {
+        blinky::BlinkyConfig {
+            on_duration_ms: self.on_duration_ms as u64,
+            off_duration_ms: self.off_duration_ms as u64,
+            count,
+        }
+    }
+
+    /// Apply `f` to both durations, clamping each result to at least 1ms
+    pub fn map_durations(&self, f: impl Fn(u32) -> u32) -> BlinkyConfig {
+        Self::new(
+            f(self.on_duration_ms).max(1),
+            f(self.off_duration_ms).max(1),
+        )
+    }
+
+    /// Interpolate toward `other` by the fixed-point fraction `t_q8 / 255`
+    ///
+    /// `t_q8 = 0` returns this config and `t_q8 = 255` returns `other`.
+    /// Uses integer math only; results are clamped to at least 1ms.
+    pub fn lerp(&self, other: &BlinkyConfig, t_q8: u8) -> BlinkyConfig {
+        let mix = |from: u32, to: u32| {
+            let delta = to as i64 - from as i64;
+            let value = from as i64 + delta * t_q8 as i64 / 255;
+            value.max(1) as u32
+        };
+
+        Self::new(
+            mix(self.on_duration_ms, other.on_duration_ms),
+            mix(self.off_duration_ms, other.off_duration_ms),
+        )
+    }
+
+    /// Round both durations to the nearest multiple of `grid_ms`
+    ///
+    /// Durations never drop below `grid_ms`, so a quantized config stays
+    /// nonzero. A `grid_ms` of zero returns the config unchanged.
+    pub fn quantize(&self, grid_ms: u32) -> BlinkyConfig {
+        if grid_ms == 0 {
+            return *self;
+        }
+
+        let round = |duration_ms: u32| {
+            let steps = duration_ms.saturating_add(grid_ms / 2) / grid_ms;
+            steps.max(1).saturating_mul(grid_ms)
+        };
+
+        Self::new(round(self.on_duration_ms), round(self.off_duration_ms))
+    }
+}
+
+/// A configuration with a short human-readable label
+#[derive(Debug, Clone)]
+pub struct NamedConfig {
+    name: heapless::String<16>,
+    config: BlinkyConfig,
+}
+
+impl NamedConfig {
+    /// Create a named config; names longer than 16 bytes are rejected
+    pub fn new(name: &str, config: BlinkyConfig) -> Result<Self, BlinkyError> {
+        let name = heapless::String::try_from(name).map_err(|_| BlinkyError::NameTooLong)?;
+        Ok(Self { name, config })
+    }
+
+    /// Get the label
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> BlinkyConfig {
+        self.config
+    }
+}
+
+impl fmt::Display for NamedConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: ON={}ms OFF={}ms",
+            self.name, self.config.on_duration_ms, self.config.off_duration_ms
+        )
+    }
+}
+
+/// Builder that validates each duration as it is set
+///
+/// Unlike constructing a `BlinkyConfig` directly, invalid values are
+/// rejected immediately so interactive callers get instant feedback.
+/// Unset durations keep their default values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatingBuilder {
+    config: BlinkyConfig,
+}
+
+impl ValidatingBuilder {
+    /// Create a builder starting from the default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the ON duration, rejecting zero
+    pub fn set_on_ms(&mut self, on_duration_ms: u32) -> Result<&mut Self, BlinkyError> {
+        if on_duration_ms == 0 {
+            return Err(BlinkyError::ZeroDuration);
+        }
+        self.config.on_duration_ms = on_duration_ms;
+        Ok(self)
+    }
+
+    /// Set the OFF duration, rejecting zero
+    pub fn set_off_ms(&mut self, off_duration_ms: u32) -> Result<&mut Self, BlinkyError> {
+        if off_duration_ms == 0 {
+            return Err(BlinkyError::ZeroDuration);
+        }
+        self.config.off_duration_ms = off_duration_ms;
+        Ok(self)
+    }
+
+    /// Return the built configuration
+    pub fn finish(&self) -> BlinkyConfig {
+        self.config
+    }
 }
 
 /// A pattern generator for LED blinking
@@ -102,6 +611,10 @@ pub struct BlinkyPattern {
     state: BlinkyState,
     config: BlinkyConfig,
     pub(crate) cycle_count: u32,
+    count_modulus: Option<u32>,
+    step_count: u32,
+    actual_elapsed_ms: u64,
+    expected_elapsed_ms: u64,
 }
 
 impl BlinkyPattern {
@@ -111,6 +624,10 @@ impl BlinkyPattern {
             state: BlinkyState::Off,
             config,
             cycle_count: 0,
+            count_modulus: None,
+            step_count: 0,
+            actual_elapsed_ms: 0,
+            expected_elapsed_ms: 0,
         }
     }
 
@@ -124,6 +641,11 @@ impl BlinkyPattern {
         self.cycle_count
     }
 
+    /// Get the total number of `next()` calls, saturating at `u32::MAX`
+    pub fn step_count(&self) -> u32 {
+        self.step_count
+    }
+
     /// Get the configuration
     pub fn config(&self) -> BlinkyConfig {
         self.config
@@ -132,20 +654,124 @@ impl BlinkyPattern {
     /// Advance to the next state and return the duration to wait
     pub fn next(&mut self) -> (BlinkyState, u32) {
         self.state.toggle();
+        self.step_count = self.step_count.saturating_add(1);
 
         // Increment cycle count when transitioning to ON
         if self.state == BlinkyState::On {
-            self.cycle_count = self.cycle_count.saturating_add(1);
+            self.cycle_count = match self.count_modulus {
+                Some(modulus) if modulus > 0 => (self.cycle_count % modulus + 1) % modulus,
+                _ => self.cycle_count.saturating_add(1),
+            };
         }
 
         let duration = self.config.duration_for_state(self.state);
         (self.state, duration)
     }
 
+    /// Advance like `next`, reporting the transition as an edge
+    pub fn next_edge(&mut self) -> (Edge, u32) {
+        let (state, duration) = self.next();
+        let edge = match state {
+            BlinkyState::On => Edge::Rising,
+            BlinkyState::Off => Edge::Falling,
+        };
+        (edge, duration)
+    }
+
+    /// Advance from an external tick source, correcting for timer drift
+    ///
+    /// `actual_elapsed_ms` is the measured time since the previous tick.
+    /// Transitions happen when the cumulative measured time crosses the
+    /// cumulative ideal schedule, so jitter does not accumulate. The first
+    /// tick starts the pattern. At most one transition happens per tick;
+    /// any lag carries over to the next one.
+    /// Returns the new state if a transition happened.
+    pub fn tick_corrected(&mut self, actual_elapsed_ms: u32) -> Option<BlinkyState> {
+        self.actual_elapsed_ms = self
+            .actual_elapsed_ms
+            .saturating_add(actual_elapsed_ms as u64);
+
+        if self.actual_elapsed_ms < self.expected_elapsed_ms {
+            return None;
+        }
+
+        let (state, duration) = self.next();
+        self.expected_elapsed_ms = self.expected_elapsed_ms.saturating_add(duration as u64);
+        Some(state)
+    }
+
+    /// Make the cycle counter wrap at `modulus` instead of saturating
+    ///
+    /// `None` (the default) restores saturating behavior. A modulus of zero
+    /// is treated the same as `None`.
+    pub fn set_count_modulus(&mut self, modulus: Option<u32>) {
+        self.count_modulus = modulus;
+    }
+
+    /// Compute the state after `n` more calls to `next()` without advancing
+    pub fn state_at_step(&self, n: u32) -> BlinkyState {
+        if n % 2 == 0 {
+            self.state
+        } else {
+            self.state.next()
+        }
+    }
+
+    /// Milliseconds until the LED next turns on
+    ///
+    /// Assumes a `next()` call just happened, so the current state has its
+    /// full duration remaining. From Off that is the Off duration; from On it
+    /// is the On duration plus the following Off duration.
+    pub fn ms_until_next_on(&self) -> u32 {
+        match self.state {
+            BlinkyState::Off => self.config.off_duration_ms,
+            BlinkyState::On => self
+                .config
+                .on_duration_ms
+                .saturating_add(self.config.off_duration_ms),
+        }
+    }
+
+    /// On-time left in the current cycle given time already spent in this state
+    ///
+    /// While On this is the remaining On duration; while Off the next On
+    /// period has not started, so the full On duration is returned.
+    pub fn remaining_on_ms(&self, elapsed_in_state_ms: u32) -> u32 {
+        match self.state {
+            BlinkyState::On => self
+                .config
+                .on_duration_ms
+                .saturating_sub(elapsed_in_state_ms),
+            BlinkyState::Off => self.config.on_duration_ms,
+        }
+    }
+
+    /// Render the configured pattern as an ASCII timeline
+    ///
+    /// Each cycle is drawn as `#` for the On duration followed by `_` for
+    /// the Off duration, using `chars_per_100ms` characters per 100ms.
+    /// Rendering always starts at the beginning of a cycle.
+    #[cfg(feature = "std")]
+    pub fn render_ascii(&self, cycles: usize, chars_per_100ms: usize) -> String {
+        let width = |duration_ms: u32| duration_ms as usize * chars_per_100ms / 100;
+        let on = "#".repeat(width(self.config.on_duration_ms));
+        let off = "_".repeat(width(self.config.off_duration_ms));
+
+        let mut timeline = String::with_capacity((on.len() + off.len()) * cycles);
+        for _ in 0..cycles {
+            timeline.push_str(&on);
+            timeline.push_str(&off);
+        }
+        timeline
+    }
+
     /// Reset the pattern to initial state
     pub fn reset(&mut self) {
         self.state = BlinkyState::Off;
         self.cycle_count = 0;
+        self.step_count = 0;
+        self.actual_elapsed_ms = 0;
+        self.expected_elapsed_ms = 0;
     }
 
     /// Set cycle count for testing purposes (only available in test builds)
@@ -155,6 +781,195 @@ impl BlinkyPattern {
     }
 }
 
+/// Looping playback of a sampled frame
+///
+/// Plays frames produced by `BlinkyConfig::to_frame`, or hand-drawn bit
+/// patterns, one sample per step. An empty frame plays as permanently off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FramePlayer<const N: usize> {
+    frame: [bool; N],
+    sample_ms: u32,
+    position: usize,
+}
+
+impl<const N: usize> FramePlayer<N> {
+    /// Create a player starting at the first sample
+    pub const fn new(frame: [bool; N], sample_ms: u32) -> Self {
+        Self {
+            frame,
+            sample_ms,
+            position: 0,
+        }
+    }
+
+    /// Emit the current sample and advance, wrapping at the end
+    /// Returns whether the LED is on and how long to hold it
+    fn advance(&mut self) -> (bool, u32) {
+        if N == 0 {
+            return (false, self.sample_ms);
+        }
+
+        let on = self.frame[self.position];
+        self.position = (self.position + 1) % N;
+        (on, self.sample_ms)
+    }
+}
+
+impl<const N: usize> Iterator for FramePlayer<N> {
+    type Item = (bool, u32);
+
+    /// Never returns `None`; the pattern runs forever
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.advance())
+    }
+}
+
+/// Running statistics over a stream of delay values
+///
+/// Uses Welford's algorithm so min, max, mean and variance can be tracked
+/// without storing the samples.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DelayStats {
+    count: u32,
+    min_ms: u32,
+    max_ms: u32,
+    mean_ms: f32,
+    m2: f32,
+}
+
+impl DelayStats {
+    /// Create an empty statistics accumulator
+    pub const fn new() -> Self {
+        Self {
+            count: 0,
+            min_ms: 0,
+            max_ms: 0,
+            mean_ms: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    /// Record a single delay value
+    pub fn record(&mut self, delay_ms: u32) {
+        if self.count == 0 {
+            self.min_ms = delay_ms;
+            self.max_ms = delay_ms;
+        } else {
+            self.min_ms = self.min_ms.min(delay_ms);
+            self.max_ms = self.max_ms.max(delay_ms);
+        }
+
+        self.count = self.count.saturating_add(1);
+        let value = delay_ms as f32;
+        let delta = value - self.mean_ms;
+        self.mean_ms += delta / self.count as f32;
+        self.m2 += delta * (value - self.mean_ms);
+    }
+
+    /// Number of recorded delays
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Smallest recorded delay
+    pub fn min(&self) -> Option<u32> {
+        (self.count > 0).then_some(self.min_ms)
+    }
+
+    /// Largest recorded delay
+    pub fn max(&self) -> Option<u32> {
+        (self.count > 0).then_some(self.max_ms)
+    }
+
+    /// Mean of the recorded delays
+    pub fn mean(&self) -> Option<f32> {
+        (self.count > 0).then_some(self.mean_ms)
+    }
+
+    /// Population variance of the recorded delays
+    pub fn variance(&self) -> Option<f32> {
+        (self.count > 0).then(|| self.m2 / self.count as f32)
+    }
+}
+
+/// Watchdog-style check that a pattern is still being advanced
+///
+/// Record each transition with its timestamp; the pattern is considered
+/// stalled once more than `STALL_FACTOR` times the expected duration of the
+/// current state has passed without another transition.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StallDetector {
+    last_transition: Option<(u64, BlinkyState)>,
+}
+
+impl StallDetector {
+    /// Multiple of the expected state duration tolerated before stalling
+    pub const STALL_FACTOR: u64 = 2;
+
+    /// Create a detector with no recorded transitions
+    pub const fn new() -> Self {
+        Self {
+            last_transition: None,
+        }
+    }
+
+    /// Record a transition into `state` at `now_ms`
+    pub fn record(&mut self, now_ms: u64, state: BlinkyState) {
+        self.last_transition = Some((now_ms, state));
+    }
+
+    /// Check whether the pattern is overdue for its next transition
+    /// Always false before the first recorded transition
+    pub fn is_stalled(&self, now_ms: u64, config: &BlinkyConfig) -> bool {
+        match self.last_transition {
+            Some((last_ms, state)) => {
+                let expected_ms = config.duration_for_state(state) as u64;
+                now_ms.saturating_sub(last_ms) > expected_ms * Self::STALL_FACTOR
+            }
+            None => false,
+        }
+    }
+}
+
+/// Fixed-bucket histogram of observed state durations
+///
+/// Bucket `i` counts durations in `[i * width, (i + 1) * width)`; anything
+/// beyond the last bucket is counted in the last bucket.
+#[derive(Debug, Clone, Copy)]
+pub struct DurationHistogram<const B: usize> {
+    bucket_width_ms: u32,
+    counts: [u32; B],
+}
+
+impl<const B: usize> DurationHistogram<B> {
+    /// Create an empty histogram; a zero width is treated as 1ms
+    pub const fn new(bucket_width_ms: u32) -> Self {
+        Self {
+            bucket_width_ms: if bucket_width_ms == 0 {
+                1
+            } else {
+                bucket_width_ms
+            },
+            counts: [0; B],
+        }
+    }
+
+    /// Record one measured duration
+    pub fn record(&mut self, duration_ms: u32) {
+        if B == 0 {
+            return;
+        }
+        let bucket = (duration_ms / self.bucket_width_ms) as usize;
+        let count = &mut self.counts[bucket.min(B - 1)];
+        *count = count.saturating_add(1);
+    }
+
+    /// Get the count in each bucket
+    pub fn bucket_counts(&self) -> &[u32; B] {
+        &self.counts
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,4 +1118,591 @@ mod tests {
         pattern.next(); // On
         assert_eq!(pattern.cycle_count(), u32::MAX);
     }
+
+    #[test]
+    fn test_delay_stats_empty() {
+        let stats = DelayStats::new();
+        assert_eq!(stats.count(), 0);
+        assert_eq!(stats.min(), None);
+        assert_eq!(stats.max(), None);
+        assert_eq!(stats.mean(), None);
+        assert_eq!(stats.variance(), None);
+    }
+
+    #[test]
+    fn test_delay_stats_known_values() {
+        let mut stats = DelayStats::new();
+        for delay in [98, 102, 100, 96, 104] {
+            stats.record(delay);
+        }
+
+        assert_eq!(stats.count(), 5);
+        assert_eq!(stats.min(), Some(96));
+        assert_eq!(stats.max(), Some(104));
+        assert!((stats.mean().unwrap() - 100.0).abs() < 0.001);
+        assert!((stats.variance().unwrap() - 8.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_blinky_config_quantize() {
+        let config = BlinkyConfig::new(503, 5).quantize(10);
+        assert_eq!(config.on_duration_ms, 500);
+        assert_eq!(config.off_duration_ms, 10);
+
+        let config = BlinkyConfig::new(505, 14).quantize(10);
+        assert_eq!(config.on_duration_ms, 510);
+        assert_eq!(config.off_duration_ms, 10);
+    }
+
+    #[test]
+    fn test_blinky_config_quantize_zero_grid() {
+        let config = BlinkyConfig::new(503, 5).quantize(0);
+        assert_eq!(config.on_duration_ms, 503);
+        assert_eq!(config.off_duration_ms, 5);
+    }
+
+    #[test]
+    fn test_validating_builder() {
+        let mut builder = ValidatingBuilder::new();
+        builder.set_on_ms(150).unwrap().set_off_ms(350).unwrap();
+
+        let config = builder.finish();
+        assert_eq!(config.on_duration_ms, 150);
+        assert_eq!(config.off_duration_ms, 350);
+        assert!(config.is_valid());
+    }
+
+    #[test]
+    fn test_validating_builder_rejects_zero() {
+        let mut builder = ValidatingBuilder::new();
+        assert_eq!(builder.set_on_ms(0).err(), Some(BlinkyError::ZeroDuration));
+        assert_eq!(builder.set_off_ms(0).err(), Some(BlinkyError::ZeroDuration));
+
+        // Rejected values leave the previous durations in place
+        let config = builder.finish();
+        assert_eq!(config.on_duration_ms, 500);
+        assert_eq!(config.off_duration_ms, 500);
+    }
+
+    #[test]
+    fn test_blinky_config_map_durations() {
+        let config = BlinkyConfig::new(100, 200).map_durations(|ms| ms + 50);
+        assert_eq!(config.on_duration_ms, 150);
+        assert_eq!(config.off_duration_ms, 250);
+
+        let config = BlinkyConfig::new(100, 200).map_durations(|_| 0);
+        assert_eq!(config.on_duration_ms, 1);
+        assert_eq!(config.off_duration_ms, 1);
+    }
+
+    #[test]
+    fn test_blinky_pattern_state_at_step() {
+        let mut pattern = BlinkyPattern::new(BlinkyConfig::default());
+
+        assert_eq!(pattern.state_at_step(0), BlinkyState::Off);
+        assert_eq!(pattern.state_at_step(1), BlinkyState::On);
+        assert_eq!(pattern.state_at_step(4), BlinkyState::Off);
+        assert_eq!(pattern.state_at_step(u32::MAX), BlinkyState::On);
+
+        pattern.next();
+        assert_eq!(pattern.state_at_step(2), BlinkyState::On);
+        assert_eq!(pattern.state_at_step(3), BlinkyState::Off);
+
+        // Does not mutate the pattern
+        assert_eq!(pattern.state(), BlinkyState::On);
+        assert_eq!(pattern.cycle_count(), 1);
+    }
+
+    #[test]
+    fn test_blinky_pattern_count_modulus() {
+        let mut pattern = BlinkyPattern::new(BlinkyConfig::default());
+        pattern.set_count_modulus(Some(10));
+
+        for expected in 1..=9 {
+            pattern.next(); // Off -> On
+            pattern.next(); // On -> Off
+            assert_eq!(pattern.cycle_count(), expected);
+        }
+
+        // Wraps from 9 back to 0 on the next On transition
+        pattern.next();
+        assert_eq!(pattern.cycle_count(), 0);
+        pattern.next();
+        pattern.next();
+        assert_eq!(pattern.cycle_count(), 1);
+    }
+
+    #[test]
+    fn test_blinky_pattern_count_modulus_cleared() {
+        let mut pattern = BlinkyPattern::new(BlinkyConfig::default());
+        pattern.set_count_modulus(Some(10));
+        pattern.set_count_modulus(None);
+        pattern.cycle_count = u32::MAX;
+
+        pattern.next();
+        assert_eq!(pattern.cycle_count(), u32::MAX);
+    }
+
+    #[test]
+    fn test_blinky_pattern_ms_until_next_on() {
+        let mut pattern = BlinkyPattern::new(BlinkyConfig::new(200, 800));
+
+        assert_eq!(pattern.ms_until_next_on(), 800);
+
+        pattern.next(); // Off -> On
+        assert_eq!(pattern.ms_until_next_on(), 1000);
+
+        pattern.next(); // On -> Off
+        assert_eq!(pattern.ms_until_next_on(), 800);
+    }
+
+    #[test]
+    fn test_stall_detector() {
+        let config = BlinkyConfig::new(100, 300);
+        let mut pattern = BlinkyPattern::new(config);
+        let mut detector = StallDetector::new();
+        assert!(!detector.is_stalled(10_000, &config));
+
+        let (state, _) = pattern.next();
+        detector.record(1000, state);
+        assert!(!detector.is_stalled(1100, &config));
+        assert!(!detector.is_stalled(1200, &config));
+        assert!(detector.is_stalled(1201, &config));
+
+        // Off lasts longer, so the tolerance grows with it
+        let (state, _) = pattern.next();
+        detector.record(1100, state);
+        assert!(!detector.is_stalled(1700, &config));
+        assert!(detector.is_stalled(1701, &config));
+    }
+
+    #[test]
+    fn test_blinky_config_to_controller_config() {
+        let config = BlinkyConfig::new(120, 340);
+
+        let controller_config = config.to_controller_config(Some(4));
+        assert_eq!(controller_config.on_duration_ms, 120);
+        assert_eq!(controller_config.off_duration_ms, 340);
+        assert_eq!(controller_config.count, Some(4));
+
+        assert_eq!(config.to_controller_config(None).count, None);
+    }
+
+    #[test]
+    fn test_atomic_blinky_state_round_trip() {
+        static STATE: AtomicBlinkyState = AtomicBlinkyState::new(BlinkyState::Off);
+        assert_eq!(STATE.load(), BlinkyState::Off);
+
+        STATE.store(BlinkyState::On);
+        assert_eq!(STATE.load(), BlinkyState::On);
+
+        STATE.store(BlinkyState::Off);
+        assert_eq!(STATE.load(), BlinkyState::Off);
+    }
+
+    #[test]
+    fn test_blinky_config_presets_match_default() {
+        let (name, preset) = BlinkyConfig::PRESETS[0];
+        let default = BlinkyConfig::default();
+        assert_eq!(name, "default");
+        assert_eq!(preset.on_duration_ms, default.on_duration_ms);
+        assert_eq!(preset.off_duration_ms, default.off_duration_ms);
+        assert!(BlinkyConfig::PRESETS
+            .iter()
+            .all(|(_, preset)| preset.is_valid()));
+    }
+
+    #[test]
+    fn test_blinky_config_next_preset() {
+        let next = BlinkyConfig::fast().next_preset();
+        assert_eq!(next.on_duration_ms, BlinkyConfig::slow().on_duration_ms);
+        assert_eq!(next.off_duration_ms, BlinkyConfig::slow().off_duration_ms);
+
+        // Cycling through every preset wraps back to the start
+        let mut config = BlinkyConfig::fast();
+        for _ in 0..BlinkyConfig::PRESETS.len() {
+            config = config.next_preset();
+        }
+        assert_eq!(config.on_duration_ms, BlinkyConfig::fast().on_duration_ms);
+
+        let heartbeat = BlinkyConfig::heartbeat();
+        assert_eq!(heartbeat.next_preset().on_duration_ms, 500);
+
+        let custom = BlinkyConfig::new(123, 456);
+        assert_eq!(custom.next_preset().on_duration_ms, 500);
+    }
+
+    #[test]
+    fn test_duration_histogram_buckets() {
+        let mut histogram = DurationHistogram::<4>::new(50);
+        for duration in [0, 49, 50, 99, 100, 120, 149, 150, 1000] {
+            histogram.record(duration);
+        }
+
+        assert_eq!(histogram.bucket_counts(), &[2, 2, 3, 2]);
+    }
+
+    #[test]
+    fn test_blinky_pattern_remaining_on_ms() {
+        let mut pattern = BlinkyPattern::new(BlinkyConfig::new(300, 700));
+
+        assert_eq!(pattern.remaining_on_ms(0), 300);
+        assert_eq!(pattern.remaining_on_ms(500), 300);
+
+        pattern.next(); // Off -> On
+        assert_eq!(pattern.remaining_on_ms(0), 300);
+        assert_eq!(pattern.remaining_on_ms(120), 180);
+        assert_eq!(pattern.remaining_on_ms(300), 0);
+        assert_eq!(pattern.remaining_on_ms(450), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_blinky_pattern_render_ascii() {
+        let pattern = BlinkyPattern::new(BlinkyConfig::new(200, 200));
+        assert_eq!(pattern.render_ascii(2, 1), "##__##__");
+        assert_eq!(pattern.render_ascii(1, 2), "####____");
+
+        let pattern = BlinkyPattern::new(BlinkyConfig::new(100, 300));
+        assert_eq!(pattern.render_ascii(2, 1), "#___#___");
+    }
+
+    #[test]
+    fn test_blinky_pattern_tick_corrected() {
+        let mut pattern = BlinkyPattern::new(BlinkyConfig::new(100, 100));
+
+        assert_eq!(pattern.tick_corrected(0), Some(BlinkyState::On));
+        assert_eq!(pattern.tick_corrected(60), None);
+        assert_eq!(pattern.tick_corrected(45), Some(BlinkyState::Off));
+
+        // Ran 5ms late, so the next boundary is still at 200ms overall
+        assert_eq!(pattern.tick_corrected(90), None);
+        assert_eq!(pattern.tick_corrected(5), Some(BlinkyState::On));
+    }
+
+    #[test]
+    fn test_blinky_pattern_tick_corrected_long_run() {
+        let mut pattern = BlinkyPattern::new(BlinkyConfig::new(100, 100));
+        let mut now_ms = 0u64;
+        let mut on_times = Vec::new();
+
+        // Jittery 7ms/13ms ticks averaging 10ms
+        for i in 0..10_000u32 {
+            let elapsed = if i % 2 == 0 { 7 } else { 13 };
+            now_ms += elapsed as u64;
+            if pattern.tick_corrected(elapsed) == Some(BlinkyState::On) {
+                on_times.push(now_ms);
+            }
+        }
+
+        let cycles = (on_times.len() - 1) as f32;
+        let average_period = (on_times[on_times.len() - 1] - on_times[0]) as f32 / cycles;
+        assert!((average_period - 200.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_blinky_config_round_to_tick() {
+        // Millisecond ticks leave whole-millisecond durations unchanged
+        let config = BlinkyConfig::new(503, 7).round_to_tick(1000);
+        assert_eq!(config.on_duration_ms, 503);
+        assert_eq!(config.off_duration_ms, 7);
+
+        // 32.768kHz ticks are finer than 1ms, so values stay aligned
+        let config = BlinkyConfig::new(1000, 1).round_to_tick(32_768);
+        assert_eq!(config.on_duration_ms, 1000);
+        assert_eq!(config.off_duration_ms, 1);
+
+        // Coarse 100Hz ticks snap to 10ms steps and never reach zero
+        let config = BlinkyConfig::new(503, 2).round_to_tick(100);
+        assert_eq!(config.on_duration_ms, 500);
+        assert_eq!(config.off_duration_ms, 10);
+
+        let config = BlinkyConfig::new(503, 2).round_to_tick(0);
+        assert_eq!(config.on_duration_ms, 503);
+    }
+
+    #[test]
+    fn test_blinky_config_durations() {
+        let (on, off) = BlinkyConfig::new(200, 800).durations();
+        assert_eq!(on, 200);
+        assert_eq!(off, 800);
+    }
+
+    #[test]
+    fn test_blinky_config_lerp() {
+        let from = BlinkyConfig::new(100, 1000);
+        let to = BlinkyConfig::new(610, 490);
+
+        assert_eq!(from.lerp(&to, 0).durations(), (100, 1000));
+        assert_eq!(from.lerp(&to, 255).durations(), (610, 490));
+
+        // 128/255 of the way there
+        assert_eq!(from.lerp(&to, 128).durations(), (356, 744));
+    }
+
+    #[test]
+    fn test_blinky_config_perceived_state() {
+        assert_eq!(
+            BlinkyConfig::new(1000, 10).perceived_state(),
+            Some(BlinkyState::On)
+        );
+        assert_eq!(
+            BlinkyConfig::new(10, 1000).perceived_state(),
+            Some(BlinkyState::Off)
+        );
+        assert_eq!(BlinkyConfig::new(500, 500).perceived_state(), None);
+        assert_eq!(BlinkyConfig::new(1000, 30).perceived_state(), None);
+        assert_eq!(BlinkyConfig::new(0, 0).perceived_state(), None);
+    }
+
+    #[test]
+    fn test_blinky_config_for_status() {
+        let statuses = [
+            DeviceStatus::Error,
+            DeviceStatus::Warning,
+            DeviceStatus::Ok,
+            DeviceStatus::Connecting,
+        ];
+
+        for (i, &a) in statuses.iter().enumerate() {
+            let config = BlinkyConfig::for_status(a);
+            assert!(config.is_valid());
+
+            for &b in &statuses[i + 1..] {
+                assert_ne!(config.durations(), BlinkyConfig::for_status(b).durations());
+            }
+        }
+
+        assert_eq!(
+            BlinkyConfig::for_status(DeviceStatus::Error).durations(),
+            BlinkyConfig::fast().durations()
+        );
+    }
+
+    #[test]
+    fn test_sequence_distance() {
+        use BlinkyState::{Off, On};
+
+        assert_eq!(sequence_distance(&[On, Off, On], &[On, Off, On]), 0);
+        assert_eq!(sequence_distance(&[On, Off, On, Off], &[On, On, On, On]), 2);
+        assert_eq!(sequence_distance(&[On, Off], &[On, Off, On, Off]), 2);
+        assert_eq!(sequence_distance(&[Off, Off, On], &[On]), 3);
+        assert_eq!(sequence_distance(&[], &[]), 0);
+    }
+
+    #[test]
+    fn test_blinky_config_to_frame() {
+        let config = BlinkyConfig::new(100, 100);
+        assert_eq!(config.to_frame::<4>(50), [true, true, false, false]);
+
+        // Truncated when the period needs more samples than fit
+        assert_eq!(config.to_frame::<3>(25), [true, true, true]);
+
+        // Samples past one period stay off
+        assert_eq!(
+            config.to_frame::<6>(50),
+            [true, true, false, false, false, false]
+        );
+
+        assert_eq!(config.to_frame::<2>(0), [false, false]);
+    }
+
+    #[test]
+    fn test_blinky_config_samples_per_period() {
+        let config = BlinkyConfig::new(100, 100);
+        assert_eq!(config.samples_per_period(30), 7);
+        assert_eq!(config.samples_per_period(50), 4);
+        assert_eq!(config.samples_per_period(0), 0);
+
+        assert_eq!(
+            BlinkyConfig::new(u32::MAX, u32::MAX).samples_per_period(1),
+            u32::MAX
+        );
+    }
+
+    #[test]
+    fn test_named_config() {
+        let named = NamedConfig::new("heartbeat", BlinkyConfig::heartbeat()).unwrap();
+        assert_eq!(named.name(), "heartbeat");
+        assert_eq!(named.config().durations(), (100, 900));
+        assert_eq!(named.to_string(), "heartbeat: ON=100ms OFF=900ms");
+    }
+
+    #[test]
+    fn test_named_config_name_too_long() {
+        let result = NamedConfig::new("a very long preset name", BlinkyConfig::default());
+        assert_eq!(result.err(), Some(BlinkyError::NameTooLong));
+    }
+
+    #[test]
+    fn test_config_new_checked_in_const() {
+        const CHECKED: BlinkyConfig = BlinkyConfig::new_checked(250, 500);
+
+        assert_eq!(CHECKED.durations(), (250, 500));
+        assert!(CHECKED.is_valid());
+    }
+
+    #[test]
+    #[should_panic(expected = "off duration must be nonzero")]
+    fn test_config_new_checked_panics_at_runtime() {
+        let off_ms = 0;
+        let _ = BlinkyConfig::new_checked(100, off_ms);
+    }
+
+    #[test]
+    fn test_pattern_step_count() {
+        let mut pattern = BlinkyPattern::new(BlinkyConfig::default());
+
+        for _ in 0..5 {
+            pattern.next();
+        }
+
+        assert_eq!(pattern.step_count(), 5);
+        assert_eq!(pattern.cycle_count(), 3);
+
+        pattern.reset();
+        assert_eq!(pattern.step_count(), 0);
+    }
+
+    #[test]
+    fn test_config_from_bpm() {
+        let config = BlinkyConfig::from_bpm(120.0, 0.25).unwrap();
+        assert_eq!(config.durations(), (125, 375));
+        assert_eq!(config.bpm(), 120.0);
+
+        let round_trip = BlinkyConfig::from_bpm(config.bpm(), 0.25).unwrap();
+        assert_eq!(round_trip.durations(), config.durations());
+
+        assert_eq!(
+            BlinkyConfig::from_bpm(0.0, 0.5).unwrap_err(),
+            BlinkyError::InvalidConfig
+        );
+        assert_eq!(
+            BlinkyConfig::from_bpm(f32::NAN, 0.5).unwrap_err(),
+            BlinkyError::InvalidConfig
+        );
+        assert_eq!(
+            BlinkyConfig::from_bpm(120.0, 1.0).unwrap_err(),
+            BlinkyError::InvalidConfig
+        );
+        assert_eq!(
+            BlinkyConfig::from_bpm(120.0, 0.0001).unwrap_err(),
+            BlinkyError::ZeroDuration
+        );
+        assert_eq!(BlinkyConfig::new(0, 0).bpm(), 0.0);
+    }
+
+    #[test]
+    fn test_frame_player_loops() {
+        let frame = BlinkyConfig::new(100, 50).to_frame::<3>(50);
+        let mut player = FramePlayer::new(frame, 50);
+
+        let played: Vec<_> = player.by_ref().take(7).collect();
+        assert_eq!(
+            played,
+            vec![
+                (true, 50),
+                (true, 50),
+                (false, 50),
+                (true, 50),
+                (true, 50),
+                (false, 50),
+                (true, 50),
+            ]
+        );
+
+        let mut empty = FramePlayer::new([], 20);
+        assert_eq!(empty.next(), Some((false, 20)));
+    }
+
+    #[test]
+    fn test_pattern_next_edge() {
+        let mut pattern = BlinkyPattern::new(BlinkyConfig::new(100, 300));
+
+        assert_eq!(pattern.next_edge(), (Edge::Rising, 100));
+        assert_eq!(pattern.next_edge(), (Edge::Falling, 300));
+        assert_eq!(pattern.next_edge(), (Edge::Rising, 100));
+        assert_eq!(pattern.cycle_count(), 2);
+    }
+
+    #[test]
+    fn test_random_valid_config() {
+        let mut a = 12345;
+        let mut b = 12345;
+        assert_eq!(
+            random_valid_config(&mut a).durations(),
+            random_valid_config(&mut b).durations()
+        );
+
+        let mut state = 0;
+        for _ in 0..1000 {
+            let config = random_valid_config(&mut state);
+            assert!(config.is_valid());
+            assert!(config.on_duration_ms <= 10_000);
+            assert!(config.off_duration_ms <= 10_000);
+        }
+    }
+
+    #[test]
+    fn test_run_length_encode() {
+        let runs = run_length_encode::<8>(&[true, true, false, false, false, true]);
+        assert_eq!(runs.as_slice(), &[(true, 2), (false, 3), (true, 1)]);
+
+        assert!(run_length_encode::<8>(&[]).is_empty());
+
+        // Truncated once the output is full
+        let runs = run_length_encode::<2>(&[true, false, true, false]);
+        assert_eq!(runs.as_slice(), &[(true, 1), (false, 1)]);
+
+        // Long runs are split at the counter limit
+        let long = vec![false; u16::MAX as usize + 5];
+        let runs = run_length_encode::<4>(&long);
+        assert_eq!(runs.as_slice(), &[(false, u16::MAX), (false, 5)]);
+    }
+
+    #[test]
+    fn test_frequency_from_half_period() {
+        assert_eq!(frequency_from_half_period_ms(250), 2.0);
+        assert_eq!(frequency_from_half_period_ms(500), 1.0);
+        assert_eq!(frequency_from_half_period_ms(0), 0.0);
+    }
+
+    #[test]
+    fn test_config_normalized() {
+        assert_eq!(BlinkyConfig::new(100, 100).normalized(), (0.5, 0.5));
+        assert_eq!(BlinkyConfig::new(500, 500).normalized(), (0.5, 0.5));
+        assert_eq!(BlinkyConfig::new(250, 750).normalized(), (0.25, 0.75));
+        assert_eq!(BlinkyConfig::new(0, 0).normalized(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_config_state_at() {
+        let config = BlinkyConfig::new(100, 200);
+
+        assert_eq!(config.state_at(0), BlinkyState::On);
+        assert_eq!(config.state_at(99), BlinkyState::On);
+        assert_eq!(config.state_at(100), BlinkyState::Off);
+        assert_eq!(config.state_at(299), BlinkyState::Off);
+        assert_eq!(config.state_at(300), BlinkyState::On);
+        assert_eq!(config.state_at(1210), BlinkyState::On);
+        assert_eq!(config.state_at(1150), BlinkyState::Off);
+
+        assert_eq!(BlinkyConfig::new(0, 0).state_at(10), BlinkyState::Off);
+    }
+
+    #[test]
+    fn test_state_transition_table() {
+        let table = BlinkyState::transition_table();
+        assert_eq!(
+            table,
+            [
+                (BlinkyState::Off, BlinkyState::On),
+                (BlinkyState::On, BlinkyState::Off),
+            ]
+        );
+
+        for (from, to) in table {
+            assert_eq!(from.next(), to);
+        }
+    }
 }
@@ -3,17 +3,18 @@
 
 use defmt::*;
 use embassy_executor::Spawner;
-use embassy_nrf::gpio::{Level, Output, OutputDrive};
+use embassy_nrf::gpio::Level;
 use embassy_time::Timer;
 use {defmt_rtt as _, panic_probe as _};
 
 // Import types from the library
+use active_note::board::BoardLed;
 use active_note::{BlinkyConfig, BlinkyPattern, BlinkyState};
 
 #[embassy_executor::main]
 async fn main(_spawner: Spawner) {
-    let p = embassy_nrf::init(Default::default());
-    let mut led = Output::new(p.P0_13, Level::Low, OutputDrive::Standard);
+    let mut p = embassy_nrf::init(Default::default());
+    let mut led = BoardLed::Led1.output(&mut p, Level::Low);
 
     let config = BlinkyConfig::default();
     let mut pattern = BlinkyPattern::new(config);
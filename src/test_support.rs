@@ -0,0 +1,46 @@
+//! Shared helpers for the crate's unit tests
+
+use core::future::Future;
+use core::task::{RawWaker, RawWakerVTable, Waker};
+
+use crate::blinky::AsyncDelay;
+
+/// Delay that completes immediately and records requested durations
+pub(crate) struct MockDelay {
+    pub(crate) delays: Vec<u64>,
+}
+
+impl MockDelay {
+    pub(crate) fn new() -> Self {
+        Self { delays: Vec::new() }
+    }
+}
+
+impl AsyncDelay for MockDelay {
+    async fn delay_ms(&mut self, ms: u64) {
+        self.delays.push(ms);
+    }
+}
+
+/// Waker vtable whose operations do nothing
+///
+/// Hand-built because `Waker::noop` needs Rust 1.85, above the crate's MSRV.
+const NOOP_VTABLE: RawWakerVTable = RawWakerVTable::new(
+    |_| RawWaker::new(core::ptr::null(), &NOOP_VTABLE),
+    |_| {},
+    |_| {},
+    |_| {},
+);
+
+/// Drive a future to completion; the mocks never return `Pending`
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    // SAFETY: the vtable ignores the data pointer, so a null pointer is valid
+    let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &NOOP_VTABLE)) };
+    let mut cx = core::task::Context::from_waker(&waker);
+    loop {
+        if let core::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}